@@ -0,0 +1,123 @@
+from ark_algebra_py.ark_algebra_py import bls, Scalar, G1, G2
+
+# The proof-of-possession scheme comes in two variants:
+# MinPk has public keys in G1 and signatures in G2 (this is what Ethereum uses),
+# MinSig has public keys in G2 and signatures in G1.
+MinPk = bls.MinPk
+
+# Key generation -- the input keying material must be at least 32 bytes long
+sk = MinPk.key_gen(bytes(32))
+pk = MinPk.sk_to_pk(sk)
+
+# Signing and verifying
+message = b"hello"
+signature = MinPk.sign(sk, message)
+assert(MinPk.verify(pk, message, signature))
+assert(not MinPk.verify(pk, b"goodbye", signature))
+
+# Aggregation over distinct messages
+sks = [MinPk.key_gen(bytes([i]) * 32) for i in range(3)]
+pks = [MinPk.sk_to_pk(sk) for sk in sks]
+messages = [b"a", b"b", b"c"]
+aggregate = MinPk.aggregate([MinPk.sign(sk, msg) for sk, msg in zip(sks, messages)])
+assert(MinPk.aggregate_verify(pks, messages, aggregate))
+
+# Aggregation over the same message
+aggregate = MinPk.aggregate([MinPk.sign(sk, message) for sk in sks])
+assert(MinPk.fast_aggregate_verify(pks, message, aggregate))
+
+# Proofs of possession
+proof = MinPk.pop_prove(sk)
+assert(MinPk.pop_verify(pk, proof))
+
+# The MinSig variant has the same methods
+pk = bls.MinSig.sk_to_pk(sk)
+assert(bls.MinSig.verify(pk, message, bls.MinSig.sign(sk, message)))
+
+# KeyGen vectors -- the master secret keys of the EIP-2333 test cases, which are
+# derived with KeyGen and an empty key_info
+keygen_vectors = [
+    ("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+     6083874454709270928345386274498605044986640685124978867557563392430687146096),
+    ("3141592653589793238462643383279502884197169399375105820974944592",
+     29757020647961307431480504535336562678282505419141012933316116377660817309383),
+    ("0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+     27580842291869792442942448775674722299803720648445448686099262467207037398656),
+    ("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+     19022158461524446591288038168518313374041767046816487870552872741050760015818),
+]
+for ikm, expected in keygen_vectors:
+    assert(int(MinPk.key_gen(bytes.fromhex(ikm))) == expected)
+
+try:
+    MinPk.key_gen(bytes(31))
+    assert(False)
+except ValueError:
+    pass
+
+# Sign, verify and aggregate vectors from the Ethereum consensus specs
+spec_sks = [
+    Scalar(0x263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3),
+    Scalar(0x47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138),
+    Scalar(0x328388aff0d4a5b7dc9205abd374e7e98f3cd9f3418edb4eafda5fb16473d216),
+]
+spec_pks = [G1.from_compressed_bytes(bytes.fromhex(pk)) for pk in [
+    "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+    "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+    "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+]]
+spec_messages = [bytes(32), bytes([0x56]) * 32, bytes([0xab]) * 32]
+spec_signatures = [[G2.from_compressed_bytes(bytes.fromhex(sig)) for sig in sigs] for sigs in [
+    [
+        "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+        "882730e5d03f6b42c3abc26d3372625034e1d871b65a8a6b900a56dae22da98abbe1b68f85e49fe7652a55ec3d0591c20767677e33e5cbb1207315c41a9ac03be39c2e7668edc043d6cb1d9fd93033caa8a1c5b0e84bedaeb6c64972503a43eb",
+        "91347bccf740d859038fcdcaf233eeceb2a436bcaaee9b2aa3bfb70efe29dfb2677562ccbea1c8e061fb9971b0753c240622fab78489ce96768259fc01360346da5b9f579e5da0d941e4c6ba18a0e64906082375394f337fa1af2b7127b0d121",
+    ],
+    [
+        "b23c46be3a001c63ca711f87a005c200cc550b9429d5f4eb38d74322144f1b63926da3388979e5321012fb1a0526bcd100b5ef5fe72628ce4cd5e904aeaa3279527843fae5ca9ca675f4f51ed8f83bbf7155da9ecc9663100a885d5dc6df96d9",
+        "af1390c3c47acdb37131a51216da683c509fce0e954328a59f93aebda7e4ff974ba208d9a4a2a2389f892a9d418d618418dd7f7a6bc7aa0da999a9d3a5b815bc085e14fd001f6a1948768a3f4afefc8b8240dda329f984cb345c6363272ba4fe",
+        "9674e2228034527f4c083206032b020310face156d4a4685e2fcaec2f6f3665aa635d90347b6ce124eb879266b1e801d185de36a0a289b85e9039662634f2eea1e02e670bc7ab849d006a70b2f93b84597558a05b879c8d445f387a5d5b653df",
+    ],
+    [
+        "948a7cb99f76d616c2c564ce9bf4a519f1bea6b0a624a02276443c245854219fabb8d4ce061d255af5330b078d5380681751aa7053da2c98bae898edc218c75f07e24d8802a17cd1f6833b71e58f5eb5b94208b4d0bb3848cecb075ea21be115",
+        "a4efa926610b8bd1c8330c918b7a5e9bf374e53435ef8b7ec186abf62e1b1f65aeaaeb365677ac1d1172a1f5b44b4e6d022c252c58486c0a759fbdc7de15a756acc4d343064035667a594b4c2a6f0b0b421975977f297dba63ee2f63ffe47bb6",
+        "ae82747ddeefe4fd64cf9cedb9b04ae3e8a43420cd255e3c7cd06a8d88b7c7f8638543719981c5d16fa3527c468c25f0026704a6951bde891360c7e8d12ddee0559004ccdbe6046b55bae1b257ee97f7cdb955773d7cf29adf3ccbb9975e4eb9",
+    ],
+]]
+for sk, pk, sigs in zip(spec_sks, spec_pks, spec_signatures):
+    assert(MinPk.sk_to_pk(sk) == pk)
+    for msg, sig in zip(spec_messages, sigs):
+        assert(MinPk.sign(sk, msg) == sig)
+        assert(MinPk.verify(pk, msg, sig))
+        assert(not MinPk.verify(pk, bytes([0x01]) + msg[1:], sig))
+
+# The signatures of each message by all three keys, aggregated
+aggregates_same_message = [G2.from_compressed_bytes(bytes.fromhex(sig)) for sig in [
+    "9683b3e6701f9a4b706709577963110043af78a5b41991b998475a3d3fd62abf35ce03b33908418efc95a058494a8ae504354b9f626231f6b3f3c849dfdeaf5017c4780e2aee1850ceaf4b4d9ce70971a3d2cfcd97b7e5ecf6759f8da5f76d31",
+    "ad38fc73846583b08d110d16ab1d026c6ea77ac2071e8ae832f56ac0cbcdeb9f5678ba5ce42bd8dce334cc47b5abcba40a58f7f1f80ab304193eb98836cc14d8183ec14cc77de0f80c4ffd49e168927a968b5cdaa4cf46b9805be84ad7efa77b",
+    "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930",
+]]
+for i, (msg, aggregate) in enumerate(zip(spec_messages, aggregates_same_message)):
+    assert(MinPk.aggregate([sigs[i] for sigs in spec_signatures]) == aggregate)
+    assert(MinPk.fast_aggregate_verify(spec_pks, msg, aggregate))
+    assert(not MinPk.fast_aggregate_verify(spec_pks[:2], msg, aggregate))
+
+# The signatures of message i by key i, aggregated
+aggregate_distinct_messages = G2.from_compressed_bytes(bytes.fromhex(
+    "9104e74b9dfd3ad502f25d6a5ef57db0ed7d9a0e00f3500586d8ce44231212542fcfaf87840539b398bf07626705cf1105d246ca1062c6c2e1a53029a0f790ed5e3cb1f52f8234dc5144c45fc847c0cd37a92d68e7c5ba7c648a8a339f171244"
+))
+assert(MinPk.aggregate([spec_signatures[i][i] for i in range(3)]) == aggregate_distinct_messages)
+assert(MinPk.aggregate_verify(spec_pks, spec_messages, aggregate_distinct_messages))
+assert(not MinPk.aggregate_verify(spec_pks, spec_messages[::-1], aggregate_distinct_messages))
+
+# Identity public keys and signatures, and empty lists, are rejected
+msg = spec_messages[0]
+assert(not MinPk.verify(G1.identity(), msg, G2.identity()))
+assert(not MinPk.fast_aggregate_verify([spec_pks[0], G1.identity()], msg, spec_signatures[0][0]))
+assert(not MinPk.fast_aggregate_verify([], msg, G2.identity()))
+assert(not MinPk.aggregate_verify([], [], G2.identity()))
+try:
+    MinPk.aggregate([])
+    assert(False)
+except ValueError:
+    pass
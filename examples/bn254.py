@@ -0,0 +1,24 @@
+from ark_algebra_py.ark_algebra_py import bn254
+
+# The BN254 curve has the same API as BLS12-381
+g1 = bn254.G1()
+g2 = bn254.G2()
+print("g1 generator: ", g1)
+
+# ECADD and ECMUL
+a = bn254.Scalar(1234)
+b = bn254.Scalar(5678)
+assert g1 * a + g1 * b == g1 * (a + b)
+assert bn254.G1.msm([g1, g1], [a, b]) == g1 * (a + b)
+
+# ECPAIRING checks that a product of pairings is one
+assert bn254.Pairing.multi_pairing([g1 * a, -(g1 * b)], [g2 * b, g2 * a]) == bn254.GT.one()
+
+# Serialisation -- compressed points are 32 bytes in G1 and 64 bytes in G2
+assert bn254.G1.from_compressed_bytes(g1.to_compressed_bytes()) == g1
+assert bn254.G2.from_compressed_bytes(g2.to_compressed_bytes()) == g2
+
+# Polynomials over the BN254 scalar field
+domain = bn254.Domain(4)
+p = bn254.Polynomial([a, b])
+assert domain.interpolate(p.evaluate_over_domain(domain)) == p
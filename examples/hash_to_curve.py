@@ -0,0 +1,38 @@
+from ark_algebra_py.ark_algebra_py import G1, G2, Fq, Fq2, expand_message_xmd
+
+def fq(hex_string):
+    return Fq(int(hex_string, 16))
+
+# expand_message_xmd -- RFC 9380, Appendix K.1
+dst = b"QUUX-V01-CS02-with-expander-SHA256-128"
+uniform_bytes = expand_message_xmd(b"abc", dst, 0x20)
+assert(uniform_bytes.hex() == "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+
+# Hashing to G1 -- RFC 9380, Appendix J.9.1
+dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_"
+p = G1.hash_to_curve(b"abc", dst)
+assert(p.x() == fq("03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"))
+assert(p.y() == fq("0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d"))
+assert(p == G1.hash_to_curve(b"abc", dst))
+assert(p != G1.hash_to_curve(b"abcd", dst))
+
+# Encoding to G1 -- RFC 9380, Appendix J.9.2
+# The nonuniform encoding maps a single field element instead of two
+dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_NU_"
+p = G1.encode_to_curve(b"abc", dst)
+assert(p.x() == fq("009769f3ab59bfd551d53a5f846b9984c59b97d6842b20a2c565baa167945e3d026a3755b6345df8ec7e6acb6868ae6d"))
+assert(p.y() == fq("1532c00cf61aa3d0ce3e5aa20c3b531a2abd2c770a790a2613818303c6b830ffc0ecf6c357af3317b9575c567f11cd2c"))
+assert(G1.from_compressed_bytes(p.to_compressed_bytes()) == p)
+
+# Hashing to G2 -- RFC 9380, Appendix J.10.1
+dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_"
+q = G2.hash_to_curve(b"abc", dst)
+assert(q.x() == Fq2.from_coeffs(
+    fq("02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+    fq("139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8"),
+))
+assert(q.y() == Fq2.from_coeffs(
+    fq("1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48"),
+    fq("00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16"),
+))
+assert(G2.from_compressed_bytes(q.to_compressed_bytes()) == q)
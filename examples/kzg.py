@@ -0,0 +1,68 @@
+import json
+
+from ark_algebra_py.ark_algebra_py import KZG, SRS, G1, G2, Polynomial, Scalar
+
+tau = Scalar(123456789)
+kzg = KZG.setup(8, tau)
+assert kzg.max_degree() == 8
+
+a = Polynomial([Scalar(100), Scalar(10), Scalar(1)])
+b = Polynomial.X() * Polynomial.X() * Polynomial.X() + Polynomial.constant(Scalar(5))
+
+# Commitments are evaluations "in the exponent" at tau
+commitment_a = kzg.commit(a)
+commitment_b = kzg.commit(b)
+assert commitment_a == G1() * a.evaluate(tau)
+
+# Single openings
+point = Scalar(2)
+value, proof = kzg.open(a, point)
+assert value == Scalar(124)
+assert kzg.verify(commitment_a, point, value, proof)
+assert not kzg.verify(commitment_a, point, value + Scalar(1), proof)
+
+# Batch openings of several polynomials at the same point
+challenge = Scalar.rand()
+values, proof = kzg.batch_open([a, b], point, challenge)
+assert values == [a.evaluate(point), b.evaluate(point)]
+assert kzg.batch_verify([commitment_a, commitment_b], point, values, proof, challenge)
+
+# Openings of a polynomial at several points
+points = [Scalar(1), Scalar(2), Scalar(3)]
+values, proof = kzg.multi_open(a, points)
+assert kzg.multi_verify(commitment_a, points, values, proof)
+
+# Setups can also be constructed from existing powers of tau
+loaded = KZG.load([G1() * tau ** i for i in range(9)], [G2(), G2() * tau])
+assert loaded.commit(a) == commitment_a
+
+# Structured reference strings hold the powers of tau and can check their consistency
+srs = SRS.from_tau(9, 2, tau)
+assert srs.verify()
+assert KZG.from_srs(srs).commit(a) == commitment_a
+
+# Transcripts of the Ethereum KZG ceremony also hold a witness that tau is the product
+# of the contributions of the participants
+def transcript(contributions):
+    tau = Scalar(1)
+    running_products, pot_pubkeys = [G1()], [G2()]
+    for contribution in contributions:
+        tau = tau * contribution
+        running_products.append(G1() * tau)
+        pot_pubkeys.append(G2() * contribution)
+    hex_points = lambda points: ["0x" + bytes(p.to_compressed_bytes()).hex() for p in points]
+    return json.dumps({"transcripts": [{
+        "numG1Powers": 9,
+        "powersOfTau": {
+            "G1Powers": hex_points([G1() * tau ** i for i in range(9)]),
+            "G2Powers": hex_points([G2() * tau ** i for i in range(2)]),
+        },
+        "witness": {
+            "runningProducts": hex_points(running_products),
+            "potPubkeys": hex_points(pot_pubkeys),
+        },
+    }]})
+
+assert SRS.from_transcript_json(transcript([Scalar(3), Scalar(5)]), 9).verify()
+# The contributions must be distinct, even though the chain of products is consistent
+assert not SRS.from_transcript_json(transcript([Scalar(3), Scalar(3)]), 9).verify()
@@ -0,0 +1,133 @@
+import os
+from hashlib import sha256
+
+from ark_algebra_py.ark_algebra_py import kzg4844, Scalar
+
+# The Ethereum mainnet trusted setup, in the text format used by c-kzg-4844
+setup = kzg4844.TrustedSetup.load(os.path.join(os.path.dirname(__file__), "trusted_setup.txt"))
+
+def field_element(value):
+    return value.to_bytes(kzg4844.BYTES_PER_FIELD_ELEMENT, "big")
+
+def random_blob(seed):
+    return b"".join(
+        field_element(int.from_bytes(sha256(seed + i.to_bytes(2, "big")).digest(), "big") % Scalar.MODULUS)
+        for i in range(kzg4844.FIELD_ELEMENTS_PER_BLOB)
+    )
+
+def assert_raises_value_error(f, *args):
+    try:
+        f(*args)
+        assert(False)
+    except ValueError:
+        pass
+
+# The reference values below were computed with c-kzg-4844 on the same setup and inputs
+point_at_infinity = bytes([0xc0]) + bytes(47)
+zero_blob = bytes(kzg4844.BYTES_PER_BLOB)
+z = field_element(int.from_bytes(sha256(b"z").digest(), "big") % Scalar.MODULUS)
+one = field_element(1)
+
+vectors = [
+    {
+        "blob": zero_blob,
+        "commitment": point_at_infinity.hex(),
+        "proof_at_z": (point_at_infinity.hex(), bytes(32).hex()),
+        "proof_at_one": (point_at_infinity.hex(), bytes(32).hex()),
+        "blob_proof": point_at_infinity.hex(),
+    },
+    {
+        "blob": random_blob(b"blob-0"),
+        "commitment": "96e3d4aed5cd5edd5dbbe38dfa3461e00df81b751e199aac550126a22894c41b638fb50a288dcc2935b5ba21040bdb21",
+        "proof_at_z": (
+            "af2d809b9a3718ac6ab773252c5d4091122d8c4882524362a6ec61b4f57c441fcec79b4978edc960d6e1124ae40aaad3",
+            "578b3a9af4c9fcd6e26e46610510fcb4784d829fa63c186d3c0dcc7b868eb92f",
+        ),
+        "proof_at_one": (
+            "8a5e9e2457bfea3e1dff72299c3b77de7534f4c2b1d9e2c8e6194c9008d4aad3110c98e783deca37b1a75eeaa067ffa6",
+            "52598c8389cac9dad87f99330e4764fc1a378ef0b6ad3b631b12d686ea696d66",
+        ),
+        "blob_proof": "960638d3f49c2358f0fbaf4bd865a79166fe5ff4b899e4ebb1aa4af0a566458699a202558eb831300d66277bdf2115c9",
+    },
+    {
+        "blob": random_blob(b"blob-1"),
+        "commitment": "a3e5743e1286258b20fb18b2e1f5114a2b70c9343bbaee8d166177f2c1ac346636bb22fddc712036be6fa86bc9e19117",
+        "proof_at_z": (
+            "b6691b2d152ecd56cb1ce1a489665f3508e6d335ba7a3e91bb88911a6bcd01794d9dfca4d5ca70dc56a50c6772153ae2",
+            "4e5c01fc1b0cff8b2976eaa8f1b2784946da14fcf88cb0d5dd1dffecb97969d0",
+        ),
+        "proof_at_one": (
+            "b2df28dff9a0538c4e7fe6b7910bcc0d183bccd521aad051679d3e0f2f61add4e00204ca5271924815765bb45469a220",
+            "50ccb5332748941308e76b8768b36ff7d77617ecfb1f51a3518e0134641a6dc2",
+        ),
+        "blob_proof": "916da5be01c8f2b9ecfb3ac524aa6b4f23b204ce2cd64a34ebc2c413efacb9e2a28fe4f3b925519abfb61fe5bed420c4",
+    },
+]
+
+for vector in vectors:
+    blob = vector["blob"]
+
+    # blob_to_kzg_commitment
+    commitment = setup.blob_to_kzg_commitment(blob)
+    assert(commitment.hex() == vector["commitment"])
+
+    # compute_kzg_proof / verify_kzg_proof, outside and inside of the evaluation domain
+    for point, (expected_proof, expected_y) in [(z, vector["proof_at_z"]), (one, vector["proof_at_one"])]:
+        proof, y = setup.compute_kzg_proof(blob, point)
+        assert(proof.hex() == expected_proof and y.hex() == expected_y)
+        assert(setup.verify_kzg_proof(commitment, point, y, proof))
+        wrong_y = field_element((int.from_bytes(y, "big") + 1) % Scalar.MODULUS)
+        assert(not setup.verify_kzg_proof(commitment, point, wrong_y, proof))
+
+    # compute_blob_kzg_proof / verify_blob_kzg_proof
+    proof = setup.compute_blob_kzg_proof(blob, commitment)
+    assert(proof.hex() == vector["blob_proof"])
+    assert(setup.verify_blob_kzg_proof(blob, commitment, proof))
+
+# The first point of the evaluation domain is one, where the blob's polynomial
+# evaluates to the first element of the blob
+blob = vectors[1]["blob"]
+_, y = setup.compute_kzg_proof(blob, one)
+assert(y == blob[:32])
+
+# A proof does not verify against another blob or commitment
+blobs = [vector["blob"] for vector in vectors]
+commitments = [bytes.fromhex(vector["commitment"]) for vector in vectors]
+proofs = [bytes.fromhex(vector["blob_proof"]) for vector in vectors]
+assert(not setup.verify_blob_kzg_proof(blobs[2], commitments[2], proofs[1]))
+assert(not setup.verify_blob_kzg_proof(blobs[1], commitments[2], proofs[2]))
+
+# verify_blob_kzg_proof_batch
+assert(setup.verify_blob_kzg_proof_batch(blobs, commitments, proofs))
+assert(setup.verify_blob_kzg_proof_batch([], [], []))
+assert(not setup.verify_blob_kzg_proof_batch(blobs, commitments, proofs[::-1]))
+assert_raises_value_error(setup.verify_blob_kzg_proof_batch, blobs, commitments, proofs[:2])
+
+# Invalid inputs are rejected
+for header in ["", "4096", "4096 x", "18446744073709551615 1", "1 18446744073709551615", "9223372036854775808 0"]:
+    assert_raises_value_error(kzg4844.TrustedSetup.from_text, header)
+non_canonical = field_element(Scalar.MODULUS)
+invalid_blob = non_canonical + blobs[1][32:]
+assert_raises_value_error(setup.blob_to_kzg_commitment, invalid_blob)
+assert_raises_value_error(setup.blob_to_kzg_commitment, blobs[1][:-1])
+assert_raises_value_error(setup.compute_kzg_proof, invalid_blob, z)
+assert_raises_value_error(setup.compute_kzg_proof, blobs[1], non_canonical)
+assert_raises_value_error(setup.compute_blob_kzg_proof, invalid_blob, commitments[1])
+assert_raises_value_error(setup.verify_blob_kzg_proof, invalid_blob, commitments[1], proofs[1])
+assert_raises_value_error(setup.verify_blob_kzg_proof_batch, [invalid_blob], [commitments[1]], [proofs[1]])
+
+proof, y = setup.compute_kzg_proof(blobs[1], z)
+assert_raises_value_error(setup.verify_kzg_proof, commitments[1], non_canonical, y, proof)
+assert_raises_value_error(setup.verify_kzg_proof, commitments[1], z, non_canonical, proof)
+
+# x = 1 is not the x-coordinate of a point on the curve, and the point with x = 0
+# is on the curve but not in G1
+not_on_curve = bytes([0x80]) + bytes(46) + bytes([1])
+not_in_subgroup = bytes([0x80]) + bytes(47)
+invalid_infinity = bytes([0xc0]) + bytes(46) + bytes([1])
+for invalid_point in [not_on_curve, not_in_subgroup, invalid_infinity, commitments[1][:-1]]:
+    assert_raises_value_error(setup.verify_kzg_proof, invalid_point, z, y, proof)
+    assert_raises_value_error(setup.verify_kzg_proof, commitments[1], z, y, invalid_point)
+    assert_raises_value_error(setup.compute_blob_kzg_proof, blobs[1], invalid_point)
+    assert_raises_value_error(setup.verify_blob_kzg_proof, blobs[1], invalid_point, proofs[1])
+    assert_raises_value_error(setup.verify_blob_kzg_proof, blobs[1], commitments[1], invalid_point)
@@ -0,0 +1,110 @@
+import os
+import random
+from hashlib import sha256
+
+from ark_algebra_py.ark_algebra_py import kzg4844, Scalar
+
+# The Ethereum mainnet trusted setup, in the text format used by c-kzg-4844. The
+# cell API needs the monomial G1 points, which the text format includes last.
+setup = kzg4844.TrustedSetup.load(os.path.join(os.path.dirname(__file__), "trusted_setup.txt"))
+
+def random_blob(seed):
+    return b"".join(
+        (int.from_bytes(sha256(seed + i.to_bytes(2, "big")).digest(), "big") % Scalar.MODULUS).to_bytes(32, "big")
+        for i in range(kzg4844.FIELD_ELEMENTS_PER_BLOB)
+    )
+
+def digest(values):
+    return sha256(b"".join(values)).hexdigest()
+
+def assert_raises_value_error(f, *args):
+    try:
+        f(*args)
+        assert(False)
+    except ValueError:
+        pass
+
+# The reference values below were computed with c-kzg-4844 on the same setup and
+# inputs. The cells and proofs are pinned by the SHA-256 digest of their concatenation.
+vectors = [
+    {
+        "blob": bytes(kzg4844.BYTES_PER_BLOB),
+        "commitment": "c0" + "00" * 47,
+        "cells": "8a39d2abd3999ab73c34db2476849cddf303ce389b35826850f9a700589b4a90",
+        "proofs": "6344e6aa419ed4ef15f7bf2d0cd777bee3bbb83174a612c527f4e956b7c87f95",
+    },
+    {
+        "blob": random_blob(b"blob-0"),
+        "commitment": "96e3d4aed5cd5edd5dbbe38dfa3461e00df81b751e199aac550126a22894c41b638fb50a288dcc2935b5ba21040bdb21",
+        "cells": "6e33471711774fa65bba124f167977e2d51549002c34fd6e198e57ce8d594219",
+        "proofs": "cbde8791185e26a31eb6f2545d44b512ce663928d46bce1ef31425785d412ce5",
+    },
+    {
+        "blob": random_blob(b"blob-1"),
+        "commitment": "a3e5743e1286258b20fb18b2e1f5114a2b70c9343bbaee8d166177f2c1ac346636bb22fddc712036be6fa86bc9e19117",
+        "cells": "9775c6d1ed3bacd82544787efe167eed69c2c250d5a064ae07c9af4b4fa01011",
+        "proofs": "83256fdd35656402759d14c4bc16b50c7059727fb875eb6bea649927a5f967a4",
+    },
+]
+
+rng = random.Random(7594)
+all_indices = list(range(kzg4844.CELLS_PER_EXT_BLOB))
+cells_and_proofs = []
+for vector in vectors:
+    blob = vector["blob"]
+    commitment = bytes.fromhex(vector["commitment"])
+
+    # compute_cells_and_kzg_proofs
+    cells, proofs = setup.compute_cells_and_kzg_proofs(blob)
+    assert(len(cells) == len(proofs) == kzg4844.CELLS_PER_EXT_BLOB)
+    assert(all(len(cell) == kzg4844.BYTES_PER_CELL for cell in cells))
+    assert(digest(cells) == vector["cells"] and digest(proofs) == vector["proofs"])
+    assert(setup.compute_cells(blob) == cells)
+
+    # The first half of the extended blob is the blob itself
+    assert(b"".join(cells[:kzg4844.CELLS_PER_EXT_BLOB // 2]) == blob)
+
+    # verify_cell_kzg_proof_batch
+    assert(setup.verify_cell_kzg_proof_batch([commitment] * len(cells), all_indices, cells, proofs))
+
+    # recover_cells_and_kzg_proofs, with 50% of the cells missing
+    indices = sorted(rng.sample(all_indices, kzg4844.CELLS_PER_EXT_BLOB // 2))
+    recovered_cells, recovered_proofs = setup.recover_cells_and_kzg_proofs(
+        indices, [cells[i] for i in indices]
+    )
+    assert(recovered_cells == cells and recovered_proofs == proofs)
+    cells_and_proofs.append((commitment, cells, proofs))
+
+# Cells of several blobs can be verified in a single batch
+commitments, indices, cells, proofs = [], [], [], []
+for commitment, blob_cells, blob_proofs in cells_and_proofs:
+    for i in rng.sample(all_indices, 8):
+        commitments.append(commitment)
+        indices.append(i)
+        cells.append(blob_cells[i])
+        proofs.append(blob_proofs[i])
+assert(setup.verify_cell_kzg_proof_batch(commitments, indices, cells, proofs))
+assert(setup.verify_cell_kzg_proof_batch([], [], [], []))
+
+# A cell does not verify at another index, against another commitment or with
+# another proof
+assert(not setup.verify_cell_kzg_proof_batch(commitments, indices[::-1], cells, proofs))
+assert(not setup.verify_cell_kzg_proof_batch(commitments[::-1], indices, cells, proofs))
+assert(not setup.verify_cell_kzg_proof_batch(commitments, indices, cells, proofs[::-1]))
+
+# Invalid inputs are rejected
+commitment, blob_cells, blob_proofs = cells_and_proofs[1]
+non_canonical = Scalar.MODULUS.to_bytes(32, "big")
+invalid_cell = non_canonical + blob_cells[0][32:]
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment], [0], [invalid_cell], [blob_proofs[0]])
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment], [0], [blob_cells[0][:-1]], [blob_proofs[0]])
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment], [kzg4844.CELLS_PER_EXT_BLOB], [blob_cells[0]], [blob_proofs[0]])
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment], [0, 1], [blob_cells[0]], [blob_proofs[0]])
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment[:-1]], [0], [blob_cells[0]], [blob_proofs[0]])
+assert_raises_value_error(setup.verify_cell_kzg_proof_batch, [commitment], [0], [blob_cells[0]], [bytes([0x80]) + bytes(47)])
+
+half = all_indices[:kzg4844.CELLS_PER_EXT_BLOB // 2]
+assert_raises_value_error(setup.recover_cells_and_kzg_proofs, half[:-1], [blob_cells[i] for i in half[:-1]])
+assert_raises_value_error(setup.recover_cells_and_kzg_proofs, half[:-1] + [0], [blob_cells[i] for i in half[:-1] + [0]])
+assert_raises_value_error(setup.recover_cells_and_kzg_proofs, half, [blob_cells[i] for i in half[:-1]])
+assert_raises_value_error(setup.recover_cells_and_kzg_proofs, half, [invalid_cell] + [blob_cells[i] for i in half[1:]])
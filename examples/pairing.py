@@ -1,4 +1,11 @@
-from ark_algebra_py.ark_algebra_py import G1, G2, GT, Scalar, Pairing
+from ark_algebra_py.ark_algebra_py import G1, G2, GT, Scalar, Pairing, Fq6, Fq12, MillerLoopOutput, G2Prepared
+
+def assert_raises_value_error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+        assert(False)
+    except ValueError:
+        pass
 
 
 # Initilisation -- This is the generator point
@@ -30,3 +37,60 @@ c_g2 = G2() *c
 
 assert(p == Pairing.pairing(c_g1, G2()))
 assert(p == Pairing.pairing(G1(), c_g2))
+
+# Serialisation -- elements of GT are compressed to 288 bytes (half the size of an
+# element of Fq12), or serialised uncompressed to 576 bytes
+compressed_bytes = p.to_compressed_bytes()
+uncompressed_bytes = p.to_uncompressed_bytes()
+assert(len(compressed_bytes) == 288 and len(uncompressed_bytes) == 576)
+assert(p == GT.from_compressed_bytes(compressed_bytes))
+assert(p == GT.from_uncompressed_bytes(uncompressed_bytes))
+assert(GT.one() == GT.from_compressed_bytes(GT.one().to_compressed_bytes()))
+assert(GT.one() == GT.from_uncompressed_bytes(GT.one().to_uncompressed_bytes()))
+
+# Deserialisation checks that the element lies in GT, and rejects other elements of
+# Fq12 and of the torus that compressed elements are decompressed into
+assert_raises_value_error(GT.from_uncompressed_bytes, Fq12.from_coeffs(Fq6.one(), Fq6.one()).to_le_bytes())
+assert_raises_value_error(GT.from_uncompressed_bytes, Fq12.rand().to_le_bytes())
+assert_raises_value_error(GT.from_compressed_bytes, Fq6.rand().to_le_bytes())
+
+# Elements of GT are hashable, and equal elements have equal hashes
+outputs = {p: "e(a * G1, b * G2)"}
+assert(outputs[Pairing.pairing(c_g1, G2())] == "e(a * G1, b * G2)")
+assert(len({gt_gen, Pairing.pairing(G1(), G2()), p}) == 2)
+
+# The Miller loop and the final exponentiation can be computed separately, to
+# accumulate Miller loops from different sources before a single final exponentiation
+mlo = Pairing.miller_loop([g], [h])
+assert(Pairing.final_exponentiation(mlo) == p)
+assert(Pairing.final_exponentiation(mlo * Pairing.miller_loop([-c_g1], [G2()])) == GT.one())
+assert(Pairing.final_exponentiation(MillerLoopOutput.one()) == GT.one())
+assert(Pairing.final_exponentiation(Pairing.miller_loop([g, G1()], [h, G2()])) == p * gt_gen)
+assert_raises_value_error(Pairing.miller_loop, [g, G1()], [h])
+
+# Points of G2 that are paired repeatedly can be prepared once. Prepared points are
+# accepted by pairing, multi_pairing and miller_loop in place of G2 points
+h_prepared = G2Prepared(h)
+assert(Pairing.pairing(g, h_prepared) == p)
+assert(Pairing.multi_pairing([g, G1()], [h_prepared, G2()]) == p * gt_gen)
+assert(Pairing.final_exponentiation(Pairing.miller_loop([g], [h_prepared])) == p)
+assert(Pairing.pairing(G1(), G2Prepared(G2.identity())) == GT.one())
+assert_raises_value_error(Pairing.multi_pairing, [g, G1()], [h_prepared])
+
+# Pairing product equations -- check tests whether the product of the pairings of
+# (G1, G2) pairs is the identity
+equation = [(g, h), (-c_g1, G2())]
+assert(Pairing.check(equation))
+assert(Pairing.check([(g, h_prepared), (-c_g1, G2())]))
+assert(Pairing.check([]))
+assert(not Pairing.check([(g, h), (c_g1, G2())]))
+
+# batch_check combines many equations into a single multi-pairing with random
+# coefficients. rng_seed makes the coefficients deterministic
+other = [(G1() * a, G2()), (G1(), -(G2() * a))]
+assert(Pairing.batch_check([equation, other], rng_seed=1234))
+assert(Pairing.batch_check([equation, other]))
+assert(Pairing.batch_check([]))
+invalid = [(g, h), (c_g1, G2())]
+for equations in [[equation, invalid], [invalid, equation], [invalid]]:
+    assert(not Pairing.batch_check(equations, rng_seed=1234))
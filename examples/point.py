@@ -1,4 +1,11 @@
-from ark_algebra_py.ark_algebra_py import G1, G2, Scalar
+from ark_algebra_py.ark_algebra_py import G1, G2, Scalar, Fq, G1FixedBase, G2FixedBase, G1MSMContext, G2MSMContext
+
+def assert_raises_value_error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+        assert(False)
+    except ValueError:
+        pass
 
 # G1 and G2 have the same methods implemented on them
 # For brevity, I will only show one method using G1 and G2 
@@ -41,8 +48,6 @@ assert(four_gen == four_gen_2)
 # Serialisation
 # 
 # serialising to/from a g1 point
-# We don't expose the uncompressed form 
-# because it seems like its not needed
 compressed_bytes = gen.to_compressed_bytes()
 deserialised_point = G1.from_compressed_bytes(compressed_bytes)
 # If the bytes being received are trusted, we can avoid
@@ -50,3 +55,115 @@ deserialised_point = G1.from_compressed_bytes(compressed_bytes)
 deserialised_point_unchecked = G1.from_compressed_bytes_unchecked(compressed_bytes)
 assert(deserialised_point == deserialised_point_unchecked)
 assert(deserialised_point == gen)
+
+# The uncompressed form holds both affine coordinates, so it is twice as long
+uncompressed_bytes = gen.to_uncompressed_bytes()
+assert(len(compressed_bytes) == 48 and len(uncompressed_bytes) == 96)
+assert(len(G2().to_compressed_bytes()) == 96 and len(G2().to_uncompressed_bytes()) == 192)
+assert(G1.from_uncompressed_bytes(uncompressed_bytes) == gen)
+assert(G1.from_uncompressed_bytes_unchecked(uncompressed_bytes) == gen)
+assert(G2.from_uncompressed_bytes(G2().to_uncompressed_bytes()) == G2())
+assert(G1.from_uncompressed_bytes(identity.to_uncompressed_bytes()) == identity)
+
+# Affine coordinates -- the identity has none
+x, y = four_gen.x(), four_gen.y()
+assert(y * y == x * x * x + Fq(4))
+assert(identity.x() is None and identity.y() is None)
+assert(G1.from_xy(x, y) == four_gen)
+assert(G1.from_xy(x, -y) == -four_gen)
+assert(G2.from_xy(G2().x(), G2().y()) == G2())
+
+# Coordinates that are not on the curve are rejected unless `check` is false
+assert_raises_value_error(G1.from_xy, x, y + Fq(1))
+assert(G1.from_xy(x, y + Fq(1), check=False) != four_gen)
+
+# Curve and subgroup checks -- the point with x = 0 is on the curve, but has order 3
+# and so is not in the prime order subgroup. Clearing the cofactor maps points into
+# the subgroup
+assert(gen.is_on_curve() and gen.is_in_correct_subgroup())
+assert(identity.is_on_curve() and identity.is_in_correct_subgroup())
+off_curve = G1.from_xy(x, y + Fq(1), check=False)
+assert(not off_curve.is_on_curve() and not off_curve.is_in_correct_subgroup())
+low_order = G1.from_xy(Fq(0), Fq(2), check=False)
+assert(low_order.is_on_curve() and not low_order.is_in_correct_subgroup())
+assert_raises_value_error(G1.from_xy, Fq(0), Fq(2))
+assert(low_order * Scalar(3) == identity)
+assert(low_order.clear_cofactor() == identity)
+mixed = low_order + gen
+assert(not mixed.is_in_correct_subgroup())
+cleared = mixed.clear_cofactor()
+assert(cleared.is_in_correct_subgroup() and not cleared.is_identity())
+assert(gen.clear_cofactor().is_in_correct_subgroup())
+assert(G2().clear_cofactor().is_in_correct_subgroup())
+
+# Batch deserialization -- the points are deserialized in parallel, and an invalid
+# point raises a ValueError naming its index
+points = [gen * Scalar(i) for i in range(1, 9)]
+compressed = [point.to_compressed_bytes() for point in points]
+assert(G1.from_compressed_bytes_batch(compressed) == points)
+assert(G1.from_compressed_bytes_batch(compressed, check=False) == points)
+assert(G1.from_compressed_bytes_batch([]) == [])
+invalid = [0x80] + [0] * 46 + [1]
+try:
+    G1.from_compressed_bytes_batch(compressed[:3] + [invalid] + compressed[3:])
+    assert(False)
+except ValueError as err:
+    assert("index 3" in str(err))
+
+# Batch serialization matches serializing each point, including the identity
+assert(G1.to_compressed_bytes_batch(points + [identity]) == compressed + [identity.to_compressed_bytes()])
+assert(G2.to_compressed_bytes_batch([G2(), G2().double()]) == [G2().to_compressed_bytes(), G2().double().to_compressed_bytes()])
+assert(G1.to_compressed_bytes_batch([]) == [])
+
+# Fixed-base multiplication -- a table of multiples of a point is precomputed once
+# and reused for every multiplication by that point
+scalars = [Scalar(i) for i in range(8)] + [Scalar.rand() for _ in range(8)]
+table = G1FixedBase(gen, 4)
+assert(table.window == 4)
+assert(all(table.mul(s) == gen * s for s in scalars))
+assert(table.batch_mul(scalars) == [gen * s for s in scalars])
+g2_table = G2FixedBase(G2(), 8)
+assert(g2_table.batch_mul(scalars) == [G2() * s for s in scalars])
+assert_raises_value_error(G1FixedBase, gen, 0)
+
+# Multi-scalar multiplication contexts -- the points are converted to affine form
+# once, and reused across multi-scalar multiplications
+def naive_msm(points, scalars):
+    result = points[0].identity()
+    for point, scalar in zip(points, scalars):
+        result = result + point * scalar
+    return result
+
+bases = [gen * Scalar.rand() for _ in range(16)]
+context = G1MSMContext(bases)
+assert(len(context) == 16)
+assert(context.msm(scalars) == naive_msm(bases, scalars))
+assert(context.msm_range(4, 12, scalars[:8]) == naive_msm(bases[4:12], scalars[:8]))
+assert(context.msm_range(3, 3, []) == identity)
+assert(context.batch_msm([scalars, scalars[::-1]]) == [naive_msm(bases, scalars), naive_msm(bases, scalars[::-1])])
+g2_bases = [G2() * s for s in scalars[:4]]
+assert(G2MSMContext(g2_bases).msm(scalars[4:8]) == naive_msm(g2_bases, scalars[4:8]))
+
+# The scalars must cover the bases exactly
+assert_raises_value_error(context.msm, scalars[:15])
+assert_raises_value_error(context.msm_range, 4, 12, scalars[:7])
+assert_raises_value_error(context.msm_range, 12, 4, [])
+assert_raises_value_error(context.msm_range, 0, 17, scalars + [Scalar(1)])
+
+# Multi-scalar multiplication matches the naive sum of products
+assert(G1.msm(bases, scalars) == naive_msm(bases, scalars))
+assert(G2.msm(g2_bases, scalars[:4]) == naive_msm(g2_bases, scalars[:4]))
+
+# Points and scalars of different lengths are rejected, unless `check` is false, in
+# which case the longer list is truncated
+assert_raises_value_error(G1.msm, bases, scalars[:15])
+assert(G1.msm(bases, scalars[:15], check=False) == naive_msm(bases[:15], scalars))
+
+# Small scalars below 2^64, and signed Python integers of any size
+small = [3, 0, 2**64 - 1, 17]
+assert(G1.msm_small(bases[:4], small) == naive_msm(bases[:4], [Scalar(k) for k in small]))
+assert_raises_value_error(G1.msm_small, bases[:3], small)
+signed = [-1, 5, -(2**200), 2**255 + 7]
+assert(G1.msm_signed(bases[:4], signed) == naive_msm(bases[:4], [Scalar(k) for k in signed]))
+assert(G1.msm_signed(bases[:2], [-1, -1]) == -(bases[0] + bases[1]))
+assert_raises_value_error(G1.msm_signed, bases[:3], signed)
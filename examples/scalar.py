@@ -1,6 +1,14 @@
-from ark_algebra_py.ark_algebra_py import Scalar
+from ark_algebra_py.ark_algebra_py import Scalar, Fq, Fq2, Fq6, Fq12, Pairing, G1, G2, expand_message_xmd
 
-# Initialisation - The default initialiser for a scalar is an u128 integer
+def assert_raises_value_error(f, *args, **kwargs):
+    try:
+        f(*args, **kwargs)
+        assert(False)
+    except ValueError:
+        pass
+
+# Initialisation - The default initialiser for a scalar is a Python integer of any
+# size, which is reduced modulo the order of the field
 scalar = Scalar(12345)
 
 # Equality -- We override eq and neq operators
@@ -21,4 +29,153 @@ assert(a + neg_a).is_zero()
 # Serialisation
 compressed_bytes = scalar.to_le_bytes()
 deserialised_scalar = Scalar.from_le_bytes(compressed_bytes)
-assert(scalar == deserialised_scalar)
\ No newline at end of file
+assert(scalar == deserialised_scalar)
+
+# Hashing to the field -- RFC 9380 hash_to_field with expand_message_xmd/SHA-256
+challenges = Scalar.hash_to_field(b"transcript", b"MY-PROTOCOL-V1", 2)
+assert(len(challenges) == 2)
+assert(challenges == Scalar.hash_to_field(b"transcript", b"MY-PROTOCOL-V1", 2))
+
+# Each scalar is reduced from L = 48 big-endian bytes of expand_message_xmd output. The
+# DST and message are those of the expand_message_xmd test vectors in RFC 9380, Appendix K.1
+dst = b"QUUX-V01-CS02-with-expander-SHA256-128"
+uniform_bytes = expand_message_xmd(b"abc", dst, 2 * 48)
+u = Scalar.hash_to_field(b"abc", dst, 2)
+assert(u == [Scalar(int.from_bytes(uniform_bytes[48 * i:48 * (i + 1)], "big")) for i in range(2)])
+assert(u == [
+    Scalar(0x13783a64573facbee9a9bccbd43bb9d34fc43913b95624bb0f093f17ccdac613),
+    Scalar(0x00bb049ee261abdc7945458195f1ad63842fcd65299ff04c03464a91f4638fc8),
+])
+
+# The base field uses L = 64, and matches u[0] and u[1] of RFC 9380, Appendix J.9.1
+u = Fq.hash_to_field(b"abc", b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_", 2)
+assert(u == [
+    Fq(0x0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951),
+    Fq(0x003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139),
+])
+
+# Base and extension fields -- Fq, Fq2, Fq6 and Fq12 have the same methods as Scalar,
+# except for sqrt, which arkworks does not implement for Fq6 and Fq12
+assert(Fq(4).sqrt() in [Fq(2), -Fq(2)])
+assert(Fq2.from_coeffs(Fq(4), Fq(0)).sqrt().square() == Fq2.from_coeffs(Fq(4), Fq(0)))
+assert(not hasattr(Fq6.one(), "sqrt") and not hasattr(Fq12.one(), "sqrt"))
+x = Fq2.from_coeffs(Fq(3), Fq(7))
+assert(x.coeffs() == [Fq(3), Fq(7)])
+assert(Fq2.from_le_bytes(x.to_le_bytes()) == x)
+assert(len(x.to_le_bytes()) == 96)
+
+# Fq2 is Fq[u] / (u^2 + 1)
+u = Fq2.from_coeffs(Fq(0), Fq(1))
+assert(u * u == -Fq2.one())
+assert(x * u == Fq2.from_coeffs(-Fq(7), Fq(3)))
+
+# The Frobenius map on Fq2 is conjugation, and x times its conjugate lies in Fq
+conjugate = x.frobenius_map(1)
+assert(conjugate == Fq2.from_coeffs(Fq(3), -Fq(7)))
+assert(x * conjugate == Fq2.from_coeffs(Fq(3 * 3 + 7 * 7), Fq(0)))
+assert(x.frobenius_map(2) == x)
+
+# Fq6 and Fq12 are built from three and two coefficients respectively
+y = Fq6.from_coeffs(x, Fq2.one(), Fq2.zero())
+z = Fq12.from_coeffs(y, Fq6.one())
+assert(z.coeffs() == [y, Fq6.one()])
+assert(z * z.inverse() == Fq12.one())
+
+# Pairing outputs are elements of Fq12 of order r, mapped to themselves by the
+# 12th power of the Frobenius map
+f = Pairing.pairing(G1(), G2()).to_fq12()
+assert(f.frobenius_map(12) == f and f != Fq12.one())
+
+# Conversion from and to Python integers -- integers of any size and sign are
+# reduced modulo the field order, unless `strict` is set
+r = Scalar.MODULUS
+assert(r == 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001)
+assert(Scalar.MODULUS_BIT_SIZE == 255 and Fq.MODULUS_BIT_SIZE == 381)
+big = 2**300 + 12345
+assert(int(Scalar(big)) == big % r)
+assert(Scalar(-1) == -Scalar(1) and int(Scalar(-1)) == r - 1)
+assert(Scalar(r) == Scalar(0) and Scalar(r + 5) == Scalar(5))
+assert(int(Scalar(12345, strict=True)) == 12345)
+for invalid in [-1, r, big]:
+    assert_raises_value_error(Scalar, invalid, strict=True)
+assert(int(Fq(-1)) == Fq.MODULUS - 1)
+
+# Field elements can be used wherever Python expects an integer
+assert(hex(Scalar(255)) == "0xff" and bin(Scalar(5)) == "0b101")
+assert([10, 20, 30][Scalar(2)] == 30)
+
+# Big-endian serialisation is the reverse of little-endian serialisation, and
+# matches int.to_bytes
+value = Scalar(big)
+assert(bytes(value.to_be_bytes()) == int(value).to_bytes(32, "big"))
+assert(value.to_be_bytes() == value.to_le_bytes()[::-1])
+assert(Scalar.from_be_bytes(value.to_be_bytes()) == value)
+assert_raises_value_error(Scalar.from_be_bytes, list(r.to_bytes(32, "big")))
+
+# Square roots and the Legendre symbol
+nine = Scalar(9)
+root = nine.sqrt()
+assert(root in [Scalar(3), -Scalar(3)])
+assert(nine.is_square() and nine.legendre() == 1)
+assert(Scalar(0).is_square() and Scalar(0).legendre() == 0 and Scalar(0).sqrt() == Scalar(0))
+non_residue = Scalar.multiplicative_generator()
+assert(not non_residue.is_square() and non_residue.legendre() == -1)
+assert(non_residue.sqrt() is None)
+assert(all(s.sqrt() * s.sqrt() == s for s in [Scalar(k) ** 2 for k in range(1, 8)]))
+assert(Fq2.from_coeffs(Fq(3), Fq(7)).square().sqrt().square() == Fq2.from_coeffs(Fq(3), Fq(7)).square())
+
+# Roots of unity -- the scalar field has roots of unity of every order 2^k for k <= 32
+omega = Scalar.get_root_of_unity(8)
+assert(omega ** 8 == Scalar(1) and omega ** 4 == -Scalar(1))
+assert(omega == Scalar.multiplicative_generator() ** ((r - 1) // 8))
+two_adic = Scalar.two_adic_root_of_unity()
+assert(two_adic ** (2**32) == Scalar(1) and two_adic ** (2**31) == -Scalar(1))
+assert(Scalar.get_root_of_unity(2**32) == two_adic)
+assert_raises_value_error(Scalar.get_root_of_unity, 2**33)
+assert_raises_value_error(Scalar.get_root_of_unity, 7)
+
+# Exponentiation -- exponents may be negative or larger than 128 bits, and by
+# Fermat's little theorem only their value modulo r - 1 matters
+base = Scalar(7)
+assert(base ** 0 == Scalar(1) and base ** 3 == Scalar(343))
+assert(base ** -1 == base.inverse() and base ** -3 == Scalar(343).inverse())
+assert(base ** (r - 1) == Scalar(1))
+assert(base ** (2**200 + 5) == base ** ((2**200 + 5) % (r - 1)))
+assert(base ** -(2**200) == (base ** (2**200)).inverse())
+assert(pow(base, 2**130) == base ** (2**130 % (r - 1)))
+try:
+    Scalar(0) ** -1
+    assert(False)
+except ZeroDivisionError:
+    pass
+
+# Python integers may be mixed with field elements on either side of an operator
+assert(base + 1 == Scalar(8) and 1 + base == Scalar(8))
+assert(base - 10 == Scalar(-3) and 10 - base == Scalar(3))
+assert(base * 2**256 == base * Scalar(2**256) and 3 * base == Scalar(21))
+assert(base / 7 == Scalar(1) and 1 / base == base.inverse())
+assert(Fq2.from_coeffs(Fq(3), Fq(7)) * 2 == Fq2.from_coeffs(Fq(6), Fq(14)))
+
+# Inversion -- inverse() maps zero to zero, while checked_inverse() raises a
+# ZeroDivisionError
+assert(Scalar(0).inverse() == Scalar(0))
+assert(base.checked_inverse() == base.inverse() and base * base.checked_inverse() == Scalar(1))
+try:
+    Scalar(0).checked_inverse()
+    assert(False)
+except ZeroDivisionError:
+    pass
+
+# Batch inversion leaves zeros as they are, like inverse(), unless `strict` is set
+elems = [Scalar(k) for k in [3, 0, 5, 0, 7]]
+assert(Scalar.batch_inverse(elems) == [e.inverse() for e in elems])
+assert(Scalar.batch_inverse_and_mul(elems, base) == [base * e.inverse() for e in elems])
+assert(Scalar.batch_inverse([]) == [])
+for f, args in [(Scalar.batch_inverse, (elems,)), (Scalar.batch_inverse_and_mul, (elems, base))]:
+    try:
+        f(*args, strict=True)
+        assert(False)
+    except ZeroDivisionError as err:
+        assert("[1, 3]" in str(err))
+nonzero = [e for e in elems if not e.is_zero()]
+assert(Scalar.batch_inverse(nonzero, strict=True) == [e.inverse() for e in nonzero])
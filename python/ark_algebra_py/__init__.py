@@ -2,6 +2,17 @@ import ark_algebra_py
 ark_algebra_py = ark_algebra_py.ark_algebra_py
 
 __doc__ = ark_algebra_py.ark_algebra_py.__doc__
-from ark_algebra_py.ark_algebra_py import G1, G2, GT, Scalar, Polynomial, Domain, Pairing
+from ark_algebra_py.ark_algebra_py import G1, G2, G1FixedBase, G2FixedBase, G1MSMContext, G2MSMContext, MSMContext, GT, MillerLoopOutput, G2Prepared, Scalar, Fq, Fq2, Fq6, Fq12, Polynomial, Domain, Pairing, KZG, SRS, expand_message_xmd, bls, bn254, kzg4844
+
+# Curves behind optional cargo features
+try:
+    from ark_algebra_py.ark_algebra_py import bls12_377
+except ImportError:
+    pass
+try:
+    from ark_algebra_py.ark_algebra_py import bw6_761
+except ImportError:
+    pass
+
 if hasattr(ark_algebra_py, "__all__"):
     __all__ = ark_algebra_py.__all__
\ No newline at end of file
@@ -0,0 +1,286 @@
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ec::{
+    hashing::curve_maps::wb::WBConfig,
+    pairing::Pairing,
+    short_weierstrass::{Affine, Projective},
+    AffineRepr,
+};
+use ark_ff::{PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use hkdf::Hkdf;
+use pyo3::PyResult;
+use sha2::{Digest, Sha256};
+
+use crate::point::Point;
+use crate::utils::{serialisation_error_to_py_err, value_error};
+use crate::wrapper::{Scalar, G1, G2};
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// The length of the output keying material used by `KeyGen`, `ceil((3 * ceil(log2(r))) / 16)`.
+const KEYGEN_OKM_LENGTH: usize = 48;
+
+/// A BLS signature variant, which fixes the groups that public keys and signatures
+/// live in, along with the domain separation tags of the proof-of-possession scheme.
+pub trait Variant {
+    type PublicKey: WBConfig<ScalarField = Fr>;
+    type Signature: WBConfig<ScalarField = Fr>;
+
+    const SIGNATURE_DST: &'static [u8];
+    const POP_DST: &'static [u8];
+
+    /// Checks whether the product of the pairings of `pks[i]` and `sigs[i]` is one.
+    fn pairing_product_is_one(
+        pks: Vec<Affine<Self::PublicKey>>,
+        sigs: Vec<Affine<Self::Signature>>,
+    ) -> bool;
+}
+
+/// Public keys are elements of G1 and signatures are elements of G2.
+pub struct MinPkVariant;
+
+impl Variant for MinPkVariant {
+    type PublicKey = ark_bls12_381::g1::Config;
+    type Signature = ark_bls12_381::g2::Config;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn pairing_product_is_one(
+        pks: Vec<Affine<Self::PublicKey>>,
+        sigs: Vec<Affine<Self::Signature>>,
+    ) -> bool {
+        Bls12_381::multi_pairing(pks, sigs).is_zero()
+    }
+}
+
+/// Public keys are elements of G2 and signatures are elements of G1.
+pub struct MinSigVariant;
+
+impl Variant for MinSigVariant {
+    type PublicKey = ark_bls12_381::g2::Config;
+    type Signature = ark_bls12_381::g1::Config;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+    fn pairing_product_is_one(
+        pks: Vec<Affine<Self::PublicKey>>,
+        sigs: Vec<Affine<Self::Signature>>,
+    ) -> bool {
+        Bls12_381::multi_pairing(sigs, pks).is_zero()
+    }
+}
+
+/// Derives a secret key from the input keying material `ikm`, as specified by
+/// `KeyGen` in draft-irtf-cfrg-bls-signature-05.
+pub fn key_gen(ikm: &[u8], key_info: &[u8]) -> PyResult<Fr> {
+    if ikm.len() < 32 {
+        return Err(value_error(
+            "the input keying material must be at least 32 bytes long",
+        ));
+    }
+
+    let mut salt = KEYGEN_SALT.to_vec();
+    loop {
+        salt = Sha256::digest(&salt).to_vec();
+        let ikm = [ikm, &[0u8]].concat();
+        let info = [key_info, &(KEYGEN_OKM_LENGTH as u16).to_be_bytes()].concat();
+
+        let mut okm = [0u8; KEYGEN_OKM_LENGTH];
+        Hkdf::<Sha256>::new(Some(&salt), &ikm)
+            .expand(&info, &mut okm)
+            .expect("48 bytes is a valid output length for HKDF-SHA-256");
+
+        let sk = Fr::from_be_bytes_mod_order(&okm);
+        if !sk.is_zero() {
+            return Ok(sk);
+        }
+    }
+}
+
+/// Checks that `pk` is a valid public key, i.e. that it is a non-identity element of
+/// the prime order subgroup.
+fn key_validate<P: WBConfig>(pk: &Affine<P>) -> bool {
+    !pk.is_zero() && subgroup_check(pk)
+}
+
+fn subgroup_check<P: WBConfig>(point: &Affine<P>) -> bool {
+    point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()
+}
+
+fn hash_to_point<P: WBConfig>(msg: &[u8], dst: &[u8]) -> PyResult<Affine<P>> {
+    Point::<Projective<P>>::hash_to_curve(msg, dst).map(|p| p.to_affine())
+}
+
+pub fn sk_to_pk<V: Variant>(sk: Fr) -> Point<Projective<V::PublicKey>> {
+    Point::generator().__mul__(sk)
+}
+
+pub fn sign<V: Variant>(sk: Fr, msg: &[u8]) -> PyResult<Point<Projective<V::Signature>>> {
+    let q = hash_to_point::<V::Signature>(msg, V::SIGNATURE_DST)?;
+    Ok(Point::Point(q * sk))
+}
+
+/// Implements `CoreAggregateVerify`, which `CoreVerify` is the single-message case of.
+fn core_aggregate_verify<V: Variant>(
+    pks: &[Point<Projective<V::PublicKey>>],
+    msgs: &[&[u8]],
+    sig: Point<Projective<V::Signature>>,
+    dst: &[u8],
+) -> PyResult<bool> {
+    if pks.is_empty() || pks.len() != msgs.len() {
+        return Ok(false);
+    }
+    let sig = sig.to_affine();
+    if !subgroup_check(&sig) {
+        return Ok(false);
+    }
+
+    let mut pks_affine = Vec::with_capacity(pks.len() + 1);
+    for pk in pks {
+        let pk = pk.to_affine();
+        if !key_validate(&pk) {
+            return Ok(false);
+        }
+        pks_affine.push(pk);
+    }
+    let mut sigs_affine = msgs
+        .iter()
+        .map(|msg| hash_to_point::<V::Signature>(msg, dst))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    pks_affine.push(-Affine::<V::PublicKey>::generator());
+    sigs_affine.push(sig);
+    Ok(V::pairing_product_is_one(pks_affine, sigs_affine))
+}
+
+pub fn verify<V: Variant>(
+    pk: Point<Projective<V::PublicKey>>,
+    msg: &[u8],
+    sig: Point<Projective<V::Signature>>,
+) -> PyResult<bool> {
+    core_aggregate_verify::<V>(&[pk], &[msg], sig, V::SIGNATURE_DST)
+}
+
+pub fn aggregate<P: WBConfig>(points: Vec<Point<Projective<P>>>) -> PyResult<Point<Projective<P>>> {
+    if points.is_empty() {
+        return Err(value_error("cannot aggregate an empty list"));
+    }
+    Ok(Point::Point(points.iter().map(|p| p.to_group()).sum()))
+}
+
+pub fn aggregate_verify<V: Variant>(
+    pks: Vec<Point<Projective<V::PublicKey>>>,
+    msgs: Vec<Vec<u8>>,
+    sig: Point<Projective<V::Signature>>,
+) -> PyResult<bool> {
+    let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+    core_aggregate_verify::<V>(&pks, &msgs, sig, V::SIGNATURE_DST)
+}
+
+pub fn fast_aggregate_verify<V: Variant>(
+    pks: Vec<Point<Projective<V::PublicKey>>>,
+    msg: &[u8],
+    sig: Point<Projective<V::Signature>>,
+) -> PyResult<bool> {
+    if pks.is_empty() || !pks.iter().all(|pk| key_validate(&pk.to_affine())) {
+        return Ok(false);
+    }
+    let aggregate_pk = aggregate(pks)?;
+    verify::<V>(aggregate_pk, msg, sig)
+}
+
+fn pk_to_bytes<V: Variant>(pk: Point<Projective<V::PublicKey>>) -> PyResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    pk.to_affine()
+        .serialize_compressed(&mut bytes)
+        .map_err(serialisation_error_to_py_err)?;
+    Ok(bytes)
+}
+
+pub fn pop_prove<V: Variant>(sk: Fr) -> PyResult<Point<Projective<V::Signature>>> {
+    let pk = pk_to_bytes::<V>(sk_to_pk::<V>(sk))?;
+    let q = hash_to_point::<V::Signature>(&pk, V::POP_DST)?;
+    Ok(Point::Point(q * sk))
+}
+
+pub fn pop_verify<V: Variant>(
+    pk: Point<Projective<V::PublicKey>>,
+    proof: Point<Projective<V::Signature>>,
+) -> PyResult<bool> {
+    let pk_bytes = pk_to_bytes::<V>(pk)?;
+    core_aggregate_verify::<V>(&[pk], &[&pk_bytes], proof, V::POP_DST)
+}
+
+macro_rules! monomorphize_bls {
+    ($struct: ident, $variant: ty, $pk: ident, $sig: ident) => {
+        /// The proof-of-possession BLS signature scheme over BLS12-381.
+        #[pyo3::pyclass]
+        pub struct $struct;
+
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Derives a secret key from at least 32 bytes of input keying material.
+            #[staticmethod]
+            #[pyo3(signature = (ikm, key_info = None))]
+            fn key_gen(ikm: &[u8], key_info: Option<&[u8]>) -> PyResult<Scalar> {
+                key_gen(ikm, key_info.unwrap_or_default()).map(Scalar)
+            }
+
+            /// Returns the public key corresponding to the secret key `sk`.
+            #[staticmethod]
+            fn sk_to_pk(sk: Scalar) -> $pk {
+                $pk(sk_to_pk::<$variant>(sk.0))
+            }
+
+            /// Signs `msg` with the secret key `sk`.
+            #[staticmethod]
+            fn sign(sk: Scalar, msg: &[u8]) -> PyResult<$sig> {
+                sign::<$variant>(sk.0, msg).map($sig)
+            }
+
+            /// Checks that `sig` is a valid signature of `msg` under the public key `pk`.
+            #[staticmethod]
+            fn verify(pk: $pk, msg: &[u8], sig: $sig) -> PyResult<bool> {
+                verify::<$variant>(pk.0, msg, sig.0)
+            }
+
+            /// Aggregates a non-empty list of signatures into a single signature.
+            #[staticmethod]
+            fn aggregate(sigs: Vec<$sig>) -> PyResult<$sig> {
+                aggregate(sigs.into_iter().map(|sig| sig.0).collect()).map($sig)
+            }
+
+            /// Checks that `sig` is a valid aggregate signature of `msgs[i]` under `pks[i]`.
+            #[staticmethod]
+            fn aggregate_verify(pks: Vec<$pk>, msgs: Vec<Vec<u8>>, sig: $sig) -> PyResult<bool> {
+                let pks = pks.into_iter().map(|pk| pk.0).collect();
+                aggregate_verify::<$variant>(pks, msgs, sig.0)
+            }
+
+            /// Checks that `sig` is a valid aggregate signature of the same `msg` under
+            /// every public key in `pks`.
+            #[staticmethod]
+            fn fast_aggregate_verify(pks: Vec<$pk>, msg: &[u8], sig: $sig) -> PyResult<bool> {
+                let pks = pks.into_iter().map(|pk| pk.0).collect();
+                fast_aggregate_verify::<$variant>(pks, msg, sig.0)
+            }
+
+            /// Proves possession of the secret key `sk`.
+            #[staticmethod]
+            fn pop_prove(sk: Scalar) -> PyResult<$sig> {
+                pop_prove::<$variant>(sk.0).map($sig)
+            }
+
+            /// Checks a proof of possession of the secret key of `pk`.
+            #[staticmethod]
+            fn pop_verify(pk: $pk, proof: $sig) -> PyResult<bool> {
+                pop_verify::<$variant>(pk.0, proof.0)
+            }
+        }
+    };
+}
+
+monomorphize_bls!(MinPk, MinPkVariant, G1, G2);
+monomorphize_bls!(MinSig, MinSigVariant, G2, G1);
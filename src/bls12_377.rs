@@ -0,0 +1,22 @@
+use ark_bls12_377::{Fr, G1Projective, G2Projective};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use num_traits::identities::{One, Zero};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const SCALAR_SIZE: usize = 32;
+
+crate::monomorphize_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_field!(Fq, ark_bls12_377::Fq, 48);
+crate::monomorphize_prime_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_prime_field!(Fq, ark_bls12_377::Fq, 48);
+crate::monomorphize_field!(Fq2, ark_bls12_377::Fq2, 96);
+crate::monomorphize_extension_field!(Fq2, ark_bls12_377::Fq2, Fq, c0, c1);
+crate::monomorphize_sqrt!(Scalar, Fr);
+crate::monomorphize_sqrt!(Fq, ark_bls12_377::Fq);
+crate::monomorphize_sqrt!(Fq2, ark_bls12_377::Fq2);
+crate::monomorphize_point!(G1, G1Projective, Scalar, Fq, 48);
+crate::monomorphize_point!(G2, G2Projective, Scalar, Fq2, 96);
+
+crate::monomorphize_pairing!(Pairing, ark_bls12_377::Bls12_377, G1, G2, 288);
+
+crate::monomorphize_poly!(Fr, Scalar);
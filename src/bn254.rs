@@ -0,0 +1,22 @@
+use ark_bn254::{Fr, G1Projective, G2Projective};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use num_traits::identities::{One, Zero};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const SCALAR_SIZE: usize = 32;
+
+crate::monomorphize_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_field!(Fq, ark_bn254::Fq, 32);
+crate::monomorphize_prime_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_prime_field!(Fq, ark_bn254::Fq, 32);
+crate::monomorphize_field!(Fq2, ark_bn254::Fq2, 64);
+crate::monomorphize_extension_field!(Fq2, ark_bn254::Fq2, Fq, c0, c1);
+crate::monomorphize_sqrt!(Scalar, Fr);
+crate::monomorphize_sqrt!(Fq, ark_bn254::Fq);
+crate::monomorphize_sqrt!(Fq2, ark_bn254::Fq2);
+crate::monomorphize_point!(G1, G1Projective, Scalar, Fq, 32);
+crate::monomorphize_point!(G2, G2Projective, Scalar, Fq2, 64);
+
+crate::monomorphize_pairing!(Pairing, ark_bn254::Bn254, G1, G2, 192);
+
+crate::monomorphize_poly!(Fr, Scalar);
@@ -0,0 +1,19 @@
+use ark_bw6_761::{Fr, G1Projective, G2Projective};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use num_traits::identities::{One, Zero};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const SCALAR_SIZE: usize = 48;
+
+crate::monomorphize_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_field!(Fq, ark_bw6_761::Fq, 96);
+crate::monomorphize_prime_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_prime_field!(Fq, ark_bw6_761::Fq, 96);
+crate::monomorphize_sqrt!(Scalar, Fr);
+crate::monomorphize_sqrt!(Fq, ark_bw6_761::Fq);
+crate::monomorphize_point!(G1, G1Projective, Scalar, Fq, 96);
+crate::monomorphize_point!(G2, G2Projective, Scalar, Fq, 96);
+
+crate::monomorphize_pairing!(Pairing, ark_bw6_761::BW6_761, G1, G2, 288);
+
+crate::monomorphize_poly!(Fr, Scalar);
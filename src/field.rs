@@ -1,22 +1,58 @@
-#[macro_export]
-macro_rules! monomorphize_field {
-    ($struct: ident, $inner: ty, $COMPRESSED_SIZE: expr) => {
-        use ark_ff::Field;
+use pyo3::types::{PyAnyMethods, PyLong};
+use pyo3::{Bound, FromPyObject, PyAny, PyResult};
 
-        use pyo3::{exceptions, pyclass, pymethods, PyResult, Python};
+/// An operand of the arithmetic operators of a field class `T`: either an element of
+/// the field or a Python integer.
+pub enum Operand<'py, T> {
+    Elem(T),
+    Int(Bound<'py, PyLong>),
+}
+
+impl<'py, T: FromPyObject<'py>> FromPyObject<'py> for Operand<'py, T> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.downcast::<PyLong>() {
+            Ok(integer) => Ok(Operand::Int(integer.clone())),
+            Err(_) => ob.extract().map(Operand::Elem),
+        }
+    }
+}
 
-        use $crate::utils::serialisation_error_to_py_err;
+/// Raises a `ZeroDivisionError` listing the positions of the zeros in `elems`, if any.
+pub fn check_nonzero<F: ark_ff::Field>(elems: &[F]) -> PyResult<()> {
+    let zeros: Vec<usize> = elems
+        .iter()
+        .enumerate()
+        .filter(|(_, elem)| elem.is_zero())
+        .map(|(i, _)| i)
+        .collect();
+    if zeros.is_empty() {
+        return Ok(());
+    }
+    Err(pyo3::exceptions::PyZeroDivisionError::new_err(format!(
+        "cannot invert the zero elements at positions {zeros:?}"
+    )))
+}
 
+#[macro_export]
+macro_rules! monomorphize_field {
+    ($struct: ident, $inner: ty, $COMPRESSED_SIZE: expr) => {
         #[derive(Copy, Clone)]
-        #[pyclass]
-        pub struct $struct($inner);
+        #[pyo3::pyclass]
+        pub struct $struct(pub(crate) $inner);
 
-        #[pymethods]
+        #[pyo3::pymethods]
         impl $struct {
-            /// Converts an `i128` into an element of the field.
+            /// Converts a Python integer into an element of the field, reducing it
+            /// modulo the characteristic. With `strict`, raises a `ValueError` unless
+            /// `0 <= integer < characteristic`.
             #[new]
-            fn from_i128(integer: i128) -> Self {
-                Self(<$inner>::from(integer))
+            #[pyo3(signature = (integer, strict = false))]
+            fn new(
+                integer: &pyo3::Bound<'_, pyo3::types::PyLong>,
+                strict: bool,
+            ) -> pyo3::PyResult<Self> {
+                $crate::utils::int_to_field(integer, strict)
+                    .map(|elem| Self(<$inner as ark_ff::Field>::from_base_prime_field(elem)))
             }
 
             /// Returns the zero element of the field.
@@ -40,34 +76,74 @@ macro_rules! monomorphize_field {
                 Self(<$inner>::rand(rng))
             }
 
-            // Overriding operators
-            fn __add__(&self, rhs: Self) -> Self {
-                Self(self.0 + rhs.0)
+            /// Hashes `msg` to `count` elements of the field under the domain separation
+            /// tag `dst`, following `hash_to_field` from RFC 9380 with
+            /// `expand_message_xmd` and SHA-256.
+            #[staticmethod]
+            fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> pyo3::PyResult<Vec<Self>> {
+                $crate::hash_to_curve::hash_to_field::<$inner>(msg, dst, count)
+                    .map(|elems| elems.into_iter().map(Self).collect())
+            }
+
+            // Overriding operators. Python integers are accepted as operands on either
+            // side, and are converted like in the constructor. There are no in-place
+            // operators: `x += y` rebinds `x` to `x + y`, so that elements stay immutable
+            // like Python numbers, which matters since they are hashable.
+            fn __add__(&self, rhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(self.0 + Self::from_operand(rhs)?))
             }
 
-            fn __sub__(&self, rhs: Self) -> Self {
-                Self(self.0 - rhs.0)
+            fn __radd__(&self, lhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(Self::from_operand(lhs)? + self.0))
             }
 
-            fn __mul__(&self, rhs: Self) -> Self {
-                Self(self.0 * rhs.0)
+            fn __sub__(&self, rhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(self.0 - Self::from_operand(rhs)?))
             }
 
-            fn __truediv__(&self, rhs: Self) -> pyo3::PyResult<Self> {
-                match self.0.inverse() {
-                    Some(inv) => Ok(Self(inv * rhs.0)),
-                    None => Err(exceptions::PyZeroDivisionError::new_err(
-                        "division by zero".to_owned(),
-                    )),
-                }
+            fn __rsub__(&self, lhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(Self::from_operand(lhs)? - self.0))
+            }
+
+            fn __mul__(&self, rhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(self.0 * Self::from_operand(rhs)?))
+            }
+
+            fn __rmul__(&self, lhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Ok(Self(Self::from_operand(lhs)? * self.0))
+            }
+
+            fn __truediv__(&self, rhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Self::divide(self.0, Self::from_operand(rhs)?).map(Self)
+            }
+
+            fn __rtruediv__(&self, lhs: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<Self> {
+                Self::divide(Self::from_operand(lhs)?, self.0).map(Self)
             }
 
             fn __neg__(&self) -> Self {
                 Self(-self.0)
             }
 
-            fn __pow__(&self, other: u64, _modulo: Option<u128>) -> Self {
-                Self(self.0.pow([other]))
+            /// Raises `self` to the power `exponent`, which may be any Python integer. A
+            /// negative exponent raises the inverse of `self`.
+            fn __pow__(
+                &self,
+                exponent: &pyo3::Bound<'_, pyo3::types::PyLong>,
+                modulo: Option<&pyo3::Bound<'_, pyo3::PyAny>>,
+            ) -> pyo3::PyResult<Self> {
+                if modulo.is_some() {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(
+                        "pow() with a modulus is not supported for field elements".to_owned(),
+                    ));
+                }
+                let (negative, limbs) = $crate::utils::int_to_sign_and_limbs(exponent)?;
+                let base = if negative {
+                    Self::divide(<$inner>::one(), self.0)?
+                } else {
+                    self.0
+                };
+                Ok(Self(ark_ff::Field::pow(&base, limbs)))
             }
 
             fn __repr__(&self) -> String {
@@ -89,11 +165,15 @@ macro_rules! monomorphize_field {
                 hasher.finish()
             }
 
-            fn __richcmp__(&self, other: Self, op: pyclass::CompareOp) -> PyResult<bool> {
+            fn __richcmp__(
+                &self,
+                other: Self,
+                op: pyo3::pyclass::CompareOp,
+            ) -> pyo3::PyResult<bool> {
                 match op {
-                    pyclass::CompareOp::Eq => Ok(self.0 == other.0),
-                    pyclass::CompareOp::Ne => Ok(self.0 != other.0),
-                    _ => Err(exceptions::PyValueError::new_err(
+                    pyo3::pyclass::CompareOp::Eq => Ok(self.0 == other.0),
+                    pyo3::pyclass::CompareOp::Ne => Ok(self.0 != other.0),
+                    _ => Err(pyo3::exceptions::PyValueError::new_err(
                         "comparison operator not implemented".to_owned(),
                     )),
                 }
@@ -101,26 +181,73 @@ macro_rules! monomorphize_field {
 
             /// Squares `self`.
             fn square(&self) -> Self {
-                Self(self.0.square())
+                Self(ark_ff::Field::square(&self.0))
             }
 
             /// Doubles `self`.
             fn double(&self) -> Self {
-                Self(self.0.double())
+                Self(ark_ff::Field::double(&self.0))
             }
 
-            /// Returns the multiplicative inverse of `self`.
+            /// Returns the multiplicative inverse of `self`, or zero if `self` is zero.
             fn inverse(&self) -> Self {
-                Self(self.0.inverse().unwrap_or_default())
+                Self(ark_ff::Field::inverse(&self.0).unwrap_or_default())
+            }
+
+            /// Returns the multiplicative inverse of `self`, raising a `ZeroDivisionError`
+            /// if `self` is zero.
+            fn checked_inverse(&self) -> pyo3::PyResult<Self> {
+                Self::divide(<$inner>::one(), self.0).map(Self)
+            }
+
+            /// Returns the Legendre symbol of `self`: 0 for zero, 1 for non-zero squares
+            /// and -1 for non-squares.
+            fn legendre(&self) -> i8 {
+                let symbol = ark_ff::Field::legendre(&self.0);
+                if symbol.is_zero() {
+                    0
+                } else if symbol.is_qr() {
+                    1
+                } else {
+                    -1
+                }
+            }
+
+            /// Checks if `self` is a square, including zero.
+            fn is_square(&self) -> bool {
+                !ark_ff::Field::legendre(&self.0).is_qnr()
+            }
+
+            /// Returns the image of `self` under the `power`-th power of the Frobenius map.
+            fn frobenius_map(&self, power: usize) -> Self {
+                Self(ark_ff::Field::frobenius_map(&self.0, power))
             }
 
             /// Inverts a batch of field elements.
             /// This is much faster than inverting each element individually.
+            /// Zeros are left as they are, unless `strict` is set, in which case a
+            /// `ZeroDivisionError` listing the positions of the zeros is raised.
+            #[staticmethod]
+            #[pyo3(signature = (elems, strict = false))]
+            fn batch_inverse(elems: Vec<Self>, strict: bool) -> pyo3::PyResult<Vec<Self>> {
+                Self::batch_inverse_and_mul(elems, Self(<$inner>::one()), strict)
+            }
+
+            /// Computes `coeff / elems[i]` for a batch of field elements, with the same
+            /// batched inversion and handling of zeros as `batch_inverse`.
             #[staticmethod]
-            fn batch_inverse(elems: Vec<Self>) -> Vec<Self> {
+            #[pyo3(signature = (elems, coeff, strict = false))]
+            fn batch_inverse_and_mul(
+                elems: Vec<Self>,
+                coeff: Self,
+                strict: bool,
+            ) -> pyo3::PyResult<Vec<Self>> {
                 let mut elems = elems.into_iter().map(|e| e.0).collect::<Vec<_>>();
-                ark_ff::batch_inversion(&mut elems);
-                elems.into_iter().map(Self).collect()
+                if strict {
+                    $crate::field::check_nonzero(&elems)?;
+                }
+                ark_ff::batch_inversion_and_mul(&mut elems, &coeff.0);
+                Ok(elems.into_iter().map(Self).collect())
             }
 
             /// Checks if `self` is zero.
@@ -134,21 +261,164 @@ macro_rules! monomorphize_field {
             }
 
             /// Converts `self` into its little-endian byte representation.
-            fn to_le_bytes(&self) -> PyResult<[u8; $COMPRESSED_SIZE]> {
+            #[allow(clippy::wrong_self_convention)]
+            fn to_le_bytes(&self) -> pyo3::PyResult<[u8; $COMPRESSED_SIZE]> {
                 let mut bytes = [0u8; $COMPRESSED_SIZE];
                 self.0
                     .serialize_compressed(&mut bytes[..])
-                    .map_err(serialisation_error_to_py_err)
+                    .map_err($crate::utils::serialisation_error_to_py_err)
                     .map(|_| bytes)
             }
 
             /// Constructs an element of the field from its little-endian byte representation.
             #[staticmethod]
-            fn from_le_bytes(bytes: [u8; $COMPRESSED_SIZE]) -> PyResult<Self> {
+            fn from_le_bytes(bytes: [u8; $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
                 <$inner>::deserialize_compressed(&bytes[..])
-                    .map_err(serialisation_error_to_py_err)
+                    .map_err($crate::utils::serialisation_error_to_py_err)
                     .map(Self)
             }
         }
+
+        impl $struct {
+            fn from_operand(operand: $crate::field::Operand<'_, Self>) -> pyo3::PyResult<$inner> {
+                match operand {
+                    $crate::field::Operand::Elem(elem) => Ok(elem.0),
+                    $crate::field::Operand::Int(integer) => {
+                        $crate::utils::int_to_field(&integer, false)
+                            .map(<$inner as ark_ff::Field>::from_base_prime_field)
+                    }
+                }
+            }
+
+            fn divide(lhs: $inner, rhs: $inner) -> pyo3::PyResult<$inner> {
+                match ark_ff::Field::inverse(&rhs) {
+                    Some(inv) => Ok(lhs * inv),
+                    None => Err(pyo3::exceptions::PyZeroDivisionError::new_err(
+                        "division by zero".to_owned(),
+                    )),
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! monomorphize_extension_field {
+    ($struct: ident, $inner: ty, $base: ident, $($coeff: ident),+) => {
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Constructs an element of the extension from its coefficients over the
+            /// base field.
+            #[staticmethod]
+            fn from_coeffs($($coeff: $base),+) -> Self {
+                Self(<$inner>::new($($coeff.0),+))
+            }
+
+            /// Returns the coefficients of `self` over the base field.
+            fn coeffs(&self) -> Vec<$base> {
+                vec![$($base(self.0.$coeff)),+]
+            }
+        }
+    };
+}
+
+/// Adds `sqrt` to fields that arkworks can compute square roots in. This excludes
+/// `Fp6`, and so `Fp12`, which have no square root precomputation.
+#[macro_export]
+macro_rules! monomorphize_sqrt {
+    ($struct: ident, $inner: ty) => {
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Returns a square root of `self`, or `None` if `self` is not a square.
+            fn sqrt(&self) -> Option<Self> {
+                <$inner as ark_ff::Field>::sqrt(&self.0).map(Self)
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! monomorphize_prime_field {
+    ($struct: ident, $inner: ty, $COMPRESSED_SIZE: expr) => {
+        #[pyo3::pymethods]
+        impl $struct {
+            /// The modulus of the field.
+            #[classattr]
+            #[pyo3(name = "MODULUS")]
+            fn modulus(py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
+                use ark_ff::PrimeField;
+                $crate::utils::bigint_to_int(py, <$inner>::MODULUS).map(Into::into)
+            }
+
+            /// The number of bits of the modulus of the field.
+            #[classattr]
+            #[pyo3(name = "MODULUS_BIT_SIZE")]
+            fn modulus_bit_size() -> u32 {
+                use ark_ff::PrimeField;
+                <$inner>::MODULUS_BIT_SIZE
+            }
+
+            /// Returns the generator of the multiplicative group of the field used to
+            /// derive the roots of unity.
+            #[staticmethod]
+            fn multiplicative_generator() -> Self {
+                use ark_ff::FftField;
+                Self(<$inner>::GENERATOR)
+            }
+
+            /// Returns the primitive root of unity of order `2^s`, where `2^s` is the
+            /// largest power of two dividing `modulus - 1`.
+            #[staticmethod]
+            fn two_adic_root_of_unity() -> Self {
+                use ark_ff::FftField;
+                Self(<$inner>::TWO_ADIC_ROOT_OF_UNITY)
+            }
+
+            /// Returns a primitive `n`-th root of unity, which is
+            /// `multiplicative_generator() ** ((modulus - 1) / n)` when `n` is a power of
+            /// two. Raises a `ValueError` if the field has no root of unity of order `n`.
+            #[staticmethod]
+            fn get_root_of_unity(n: u64) -> pyo3::PyResult<Self> {
+                use ark_ff::FftField;
+                <$inner>::get_root_of_unity(n).map(Self).ok_or_else(|| {
+                    $crate::utils::value_error(format!(
+                        "the field has no root of unity of order {n}"
+                    ))
+                })
+            }
+
+            /// Converts `self` into a Python integer in `[0, modulus)`.
+            fn __int__<'py>(
+                &self,
+                py: pyo3::Python<'py>,
+            ) -> pyo3::PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+                use ark_ff::PrimeField;
+                $crate::utils::bigint_to_int(py, self.0.into_bigint())
+            }
+
+            /// Allows elements of the field to be used wherever Python expects an
+            /// integer, e.g. in `bin` or `hex`.
+            fn __index__<'py>(
+                &self,
+                py: pyo3::Python<'py>,
+            ) -> pyo3::PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+                self.__int__(py)
+            }
+
+            /// Converts `self` into its big-endian byte representation.
+            #[allow(clippy::wrong_self_convention)]
+            fn to_be_bytes(&self) -> pyo3::PyResult<[u8; $COMPRESSED_SIZE]> {
+                let mut bytes = self.to_le_bytes()?;
+                bytes.reverse();
+                Ok(bytes)
+            }
+
+            /// Constructs an element of the field from its big-endian byte representation.
+            #[staticmethod]
+            fn from_be_bytes(mut bytes: [u8; $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
+                bytes.reverse();
+                Self::from_le_bytes(bytes)
+            }
+        }
     };
 }
@@ -0,0 +1,88 @@
+use ark_ec::{scalar_mul::fixed_base::FixedBase, CurveGroup};
+use ark_ff::PrimeField;
+use pyo3::PyResult;
+
+use crate::utils::value_error;
+
+/// The largest supported window, for which the table holds `2^24` points per window.
+const MAX_WINDOW: usize = 24;
+
+/// The multiples `j * 2^(i * window) * base` for all `0 <= j < 2^window`, so that
+/// multiplying `base` by a scalar only takes one addition per window.
+pub struct FixedBaseTable<G: CurveGroup> {
+    window: usize,
+    table: Vec<Vec<G::Affine>>,
+}
+
+impl<G: CurveGroup> FixedBaseTable<G> {
+    pub fn new(base: G, window: usize) -> PyResult<Self> {
+        if !(1..=MAX_WINDOW).contains(&window) {
+            return Err(value_error(format!(
+                "the window size must be between 1 and {MAX_WINDOW}"
+            )));
+        }
+        let table = FixedBase::get_window_table(Self::scalar_size(), window, base);
+        Ok(Self { window, table })
+    }
+
+    fn scalar_size() -> usize {
+        G::ScalarField::MODULUS_BIT_SIZE as usize
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn mul(&self, scalar: &G::ScalarField) -> G {
+        let outerc = self.table.len();
+        FixedBase::windowed_mul(outerc, self.window, &self.table, scalar)
+    }
+
+    pub fn batch_mul(&self, scalars: &[G::ScalarField]) -> Vec<G> {
+        FixedBase::msm(Self::scalar_size(), self.window, &self.table, scalars)
+    }
+}
+
+#[macro_export]
+macro_rules! monomorphize_fixed_base {
+    ($struct: ident, $point: ident, $inner: ty, $scalar: ident) => {
+        /// A table of precomputed multiples of a fixed point, to speed up repeated
+        /// multiplications of that point by scalars.
+        #[pyo3::pyclass]
+        pub struct $struct($crate::fixed_base::FixedBaseTable<$inner>);
+
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Precomputes the multiples of `point` for windows of `window` bits. The
+            /// table holds about `2^window * 255 / window` points: larger windows make
+            /// multiplications faster at the cost of memory and setup time.
+            #[new]
+            fn new(py: pyo3::Python, point: $point, window: usize) -> pyo3::PyResult<Self> {
+                py.allow_threads(|| {
+                    $crate::fixed_base::FixedBaseTable::new(point.0.to_group(), window)
+                })
+                .map(Self)
+            }
+
+            /// Returns the window size of the table.
+            #[getter]
+            fn window(&self) -> usize {
+                self.0.window()
+            }
+
+            /// Multiplies the fixed point by `scalar`.
+            fn mul(&self, scalar: $scalar) -> $point {
+                $point($crate::point::Point::Point(self.0.mul(&scalar.0)))
+            }
+
+            /// Multiplies the fixed point by each of the `scalars`, in parallel.
+            fn batch_mul(&self, py: pyo3::Python, scalars: Vec<$scalar>) -> Vec<$point> {
+                let scalars: Vec<_> = scalars.into_iter().map(|scalar| scalar.0).collect();
+                py.allow_threads(|| self.0.batch_mul(&scalars))
+                    .into_iter()
+                    .map(|point| $point($crate::point::Point::Point(point)))
+                    .collect()
+            }
+        }
+    };
+}
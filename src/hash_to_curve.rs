@@ -0,0 +1,164 @@
+use ark_ec::{
+    hashing::{
+        curve_maps::wb::{WBConfig, WBMap},
+        map_to_curve_hasher::MapToCurve,
+    },
+    short_weierstrass::Projective,
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{Field, PrimeField};
+use pyo3::{pyfunction, types::PyBytes, Bound, PyResult, Python};
+use sha2::{Digest, Sha256};
+
+use crate::point::Point;
+use crate::utils::{hash_to_curve_error_to_py_err, value_error};
+
+/// The security parameter `k` of the `BLS12381*_XMD:SHA-256_SSWU_*` suites, in bits.
+const SECURITY_PARAMETER: usize = 128;
+
+/// The output size of SHA-256, in bytes.
+const SHA256_OUTPUT_SIZE: usize = 32;
+
+/// The input block size of SHA-256, in bytes.
+const SHA256_BLOCK_SIZE: usize = 64;
+
+const MAX_DST_LENGTH: usize = 255;
+
+const LONG_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Expands `msg` into `len_in_bytes` uniformly random bytes, as specified by
+/// `expand_message_xmd` in RFC 9380, instantiated with SHA-256.
+#[pyfunction]
+pub fn expand_message_xmd<'py>(
+    py: Python<'py>,
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let uniform_bytes = expand_message(msg, dst, len_in_bytes)?;
+    Ok(PyBytes::new_bound(py, &uniform_bytes))
+}
+
+fn expand_message(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> PyResult<Vec<u8>> {
+    let ell = len_in_bytes.div_ceil(SHA256_OUTPUT_SIZE);
+    if ell > 255 || len_in_bytes > u16::MAX as usize {
+        return Err(value_error(format!(
+            "cannot expand a message into {len_in_bytes} bytes with SHA-256"
+        )));
+    }
+
+    let mut dst_prime = if dst.len() > MAX_DST_LENGTH {
+        Sha256::new()
+            .chain_update(LONG_DST_PREFIX)
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK_SIZE])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * SHA256_OUTPUT_SIZE);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(l, r)| l ^ r).collect();
+        b_i = Sha256::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Hashes `msg` to `count` elements of `F`, as specified by `hash_to_field` in
+/// RFC 9380, using `expand_message_xmd` with SHA-256.
+pub fn hash_to_field<F: Field>(msg: &[u8], dst: &[u8], count: usize) -> PyResult<Vec<F>> {
+    let m = F::extension_degree() as usize;
+    let len_per_base_elem =
+        (F::BasePrimeField::MODULUS_BIT_SIZE as usize + SECURITY_PARAMETER).div_ceil(8);
+    let uniform_bytes = expand_message(msg, dst, count * m * len_per_base_elem)?;
+
+    let elems = uniform_bytes
+        .chunks(m * len_per_base_elem)
+        .map(|elem_bytes| {
+            let base_prime_field_elems: Vec<_> = elem_bytes
+                .chunks(len_per_base_elem)
+                .map(F::BasePrimeField::from_be_bytes_mod_order)
+                .collect();
+            F::from_base_prime_field_elems(&base_prime_field_elems).unwrap()
+        })
+        .collect();
+    Ok(elems)
+}
+
+impl<P: WBConfig> Point<Projective<P>> {
+    /// Hashes `msg` to a point in the prime order subgroup using the random oracle
+    /// (`_RO_`) construction of RFC 9380.
+    pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> PyResult<Self> {
+        let mapper = WBMap::<P>::new().map_err(hash_to_curve_error_to_py_err)?;
+        let u = hash_to_field::<P::BaseField>(msg, dst, 2)?;
+        let q0 = mapper
+            .map_to_curve(u[0])
+            .map_err(hash_to_curve_error_to_py_err)?;
+        let q1 = mapper
+            .map_to_curve(u[1])
+            .map_err(hash_to_curve_error_to_py_err)?;
+        Ok(Self::Affine((q0 + q1).into_affine().clear_cofactor()))
+    }
+
+    /// Encodes `msg` to a point in the prime order subgroup using the nonuniform
+    /// (`_NU_`) construction of RFC 9380.
+    pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> PyResult<Self> {
+        let mapper = WBMap::<P>::new().map_err(hash_to_curve_error_to_py_err)?;
+        let u = hash_to_field::<P::BaseField>(msg, dst, 1)?;
+        let q = mapper
+            .map_to_curve(u[0])
+            .map_err(hash_to_curve_error_to_py_err)?;
+        Ok(Self::Affine(q.clear_cofactor()))
+    }
+}
+
+#[macro_export]
+macro_rules! monomorphize_hash_to_curve {
+    ($struct: ident, $config: ty) => {
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Hashes `msg` to a point of the group under the domain separation tag `dst`,
+            /// using the `_SSWU_RO_` suite of RFC 9380.
+            #[staticmethod]
+            fn hash_to_curve(msg: &[u8], dst: &[u8]) -> pyo3::PyResult<Self> {
+                $crate::point::Point::<ark_ec::short_weierstrass::Projective<$config>>::hash_to_curve(
+                    msg, dst,
+                )
+                .map(Self)
+            }
+
+            /// Encodes `msg` to a point of the group under the domain separation tag `dst`,
+            /// using the `_SSWU_NU_` suite of RFC 9380.
+            #[staticmethod]
+            fn encode_to_curve(msg: &[u8], dst: &[u8]) -> pyo3::PyResult<Self> {
+                $crate::point::Point::<ark_ec::short_weierstrass::Projective<$config>>::encode_to_curve(
+                    msg, dst,
+                )
+                .map(Self)
+            }
+        }
+    };
+}
@@ -0,0 +1,436 @@
+use ark_ec::{
+    pairing::Pairing, scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup, Group,
+    VariableBaseMSM,
+};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_poly::{
+    polynomial::univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    DenseUVPolynomial, Polynomial,
+};
+use pyo3::PyResult;
+
+use crate::utils::value_error;
+
+type Poly<F> = DenseOrSparsePolynomial<'static, F>;
+
+/// Computes `[generator, tau * generator, ..., tau^(count - 1) * generator]`.
+pub fn powers_of_tau<G: CurveGroup>(
+    generator: G,
+    tau: G::ScalarField,
+    count: usize,
+) -> Vec<G::Affine> {
+    let powers: Vec<G::ScalarField> =
+        std::iter::successors(Some(G::ScalarField::one()), |power| Some(*power * tau))
+            .take(count)
+            .collect();
+    let scalar_size = G::ScalarField::MODULUS_BIT_SIZE as usize;
+    let window = FixedBase::get_mul_window_size(count);
+    let table = FixedBase::get_window_table(scalar_size, window, generator);
+    G::normalize_batch(&FixedBase::msm::<G>(scalar_size, window, &table, &powers))
+}
+
+/// Divides the polynomial with coefficients `coeffs` by `X - point`, returning the
+/// quotient and the remainder, which is the evaluation at `point`.
+fn divide_by_linear<F: Field>(coeffs: &[F], point: F) -> (Vec<F>, F) {
+    let mut quotient = vec![F::zero(); coeffs.len().saturating_sub(1)];
+    let mut remainder = F::zero();
+    for (i, coeff) in coeffs.iter().enumerate().rev() {
+        if i < quotient.len() {
+            quotient[i] = remainder;
+        }
+        remainder = remainder * point + coeff;
+    }
+    (quotient, remainder)
+}
+
+/// Returns the polynomial vanishing exactly on `points`.
+pub(crate) fn vanishing_polynomial<F: Field>(points: &[F]) -> DensePolynomial<F> {
+    let mut coeffs = vec![F::one()];
+    for point in points {
+        coeffs.insert(0, F::zero());
+        for i in 0..coeffs.len() - 1 {
+            let term = coeffs[i + 1] * point;
+            coeffs[i] -= term;
+        }
+    }
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Returns the polynomial of degree less than `points.len()` interpolating `values` at
+/// `points`, or `None` if the points are not distinct.
+fn interpolate<F: Field>(points: &[F], values: &[F]) -> Option<DensePolynomial<F>> {
+    let vanishing = vanishing_polynomial(points);
+    let mut coeffs = vec![F::zero(); points.len()];
+    for (point, value) in points.iter().zip(values) {
+        let (basis, _) = divide_by_linear(&vanishing.coeffs, *point);
+        let scale = *value
+            * DensePolynomial::from_coefficients_vec(basis.clone())
+                .evaluate(point)
+                .inverse()?;
+        for (coeff, basis_coeff) in coeffs.iter_mut().zip(basis) {
+            *coeff += basis_coeff * scale;
+        }
+    }
+    Some(DensePolynomial::from_coefficients_vec(coeffs))
+}
+
+/// The KZG10 polynomial commitment scheme, with the powers of tau in both groups.
+pub struct Kzg<E: Pairing> {
+    pub(crate) powers_of_g: Vec<E::G1Affine>,
+    pub(crate) powers_of_h: Vec<E::G2Affine>,
+}
+
+impl<E: Pairing> Kzg<E> {
+    /// Generates the powers of `tau` up to `max_degree` in both groups. This is only
+    /// meant for testing, since whoever knows `tau` can forge proofs.
+    pub fn setup(max_degree: usize, tau: E::ScalarField) -> Self {
+        Self {
+            powers_of_g: powers_of_tau(E::G1::generator(), tau, max_degree + 1),
+            powers_of_h: powers_of_tau(E::G2::generator(), tau, max_degree + 1),
+        }
+    }
+
+    pub fn new(powers_of_g: Vec<E::G1Affine>, powers_of_h: Vec<E::G2Affine>) -> PyResult<Self> {
+        if powers_of_g.is_empty() || powers_of_h.len() < 2 {
+            return Err(value_error(
+                "at least one power of tau in G1 and two in G2 are needed",
+            ));
+        }
+        Ok(Self {
+            powers_of_g,
+            powers_of_h,
+        })
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_g.len() - 1
+    }
+
+    fn check_degree(&self, degree: usize) -> PyResult<()> {
+        if degree > self.max_degree() {
+            return Err(value_error(format!(
+                "the degree {degree} exceeds the maximum supported degree {}",
+                self.max_degree()
+            )));
+        }
+        Ok(())
+    }
+
+    fn commit_coeffs(&self, coeffs: &[E::ScalarField]) -> PyResult<E::G1> {
+        self.check_degree(coeffs.len().saturating_sub(1))?;
+        Ok(E::G1::msm_unchecked(&self.powers_of_g, coeffs))
+    }
+
+    pub fn commit(&self, polynomial: &Poly<E::ScalarField>) -> PyResult<E::G1> {
+        match polynomial {
+            DenseOrSparsePolynomial::DPolynomial(p) => self.commit_coeffs(&p.coeffs),
+            DenseOrSparsePolynomial::SPolynomial(p) => {
+                self.check_degree(p.degree())?;
+                let (bases, coeffs): (Vec<_>, Vec<_>) = p
+                    .iter()
+                    .map(|(i, coeff)| (self.powers_of_g[*i], *coeff))
+                    .unzip();
+                Ok(E::G1::msm_unchecked(&bases, &coeffs))
+            }
+        }
+    }
+
+    /// Returns the evaluation of `polynomial` at `point` and a proof of it.
+    pub fn open(
+        &self,
+        polynomial: &Poly<E::ScalarField>,
+        point: E::ScalarField,
+    ) -> PyResult<(E::ScalarField, E::G1)> {
+        let polynomial = DensePolynomial::from(polynomial.clone());
+        self.check_degree(polynomial.degree())?;
+        let (quotient, value) = divide_by_linear(&polynomial.coeffs, point);
+        Ok((value, self.commit_coeffs(&quotient)?))
+    }
+
+    /// Checks `e(commitment - value * G, H) = e(proof, tau * H - point * H)`.
+    pub fn verify(
+        &self,
+        commitment: E::G1,
+        point: E::ScalarField,
+        value: E::ScalarField,
+        proof: E::G1,
+    ) -> bool {
+        let g = self.powers_of_g[0];
+        let h = self.powers_of_h[0];
+        let tau_minus_point = self.powers_of_h[1].into_group() - h * point;
+        E::multi_pairing(
+            [
+                (commitment - g * value).into_affine(),
+                (-proof).into_affine(),
+            ],
+            [h, tau_minus_point.into_affine()],
+        )
+        .is_zero()
+    }
+
+    /// Opens all of `polynomials` at `point` with a single proof, for the random linear
+    /// combination of the polynomials with the powers of `challenge`.
+    pub fn batch_open(
+        &self,
+        polynomials: &[Poly<E::ScalarField>],
+        point: E::ScalarField,
+        challenge: E::ScalarField,
+    ) -> PyResult<(Vec<E::ScalarField>, E::G1)> {
+        let mut combined = DensePolynomial::zero();
+        let mut values = Vec::with_capacity(polynomials.len());
+        let mut challenge_power = E::ScalarField::one();
+        for polynomial in polynomials {
+            let polynomial = DensePolynomial::from(polynomial.clone());
+            self.check_degree(polynomial.degree())?;
+            values.push(polynomial.evaluate(&point));
+            combined += (challenge_power, &polynomial);
+            challenge_power *= challenge;
+        }
+        let (quotient, _) = divide_by_linear(&combined.coeffs, point);
+        Ok((values, self.commit_coeffs(&quotient)?))
+    }
+
+    pub fn batch_verify(
+        &self,
+        commitments: &[E::G1],
+        point: E::ScalarField,
+        values: &[E::ScalarField],
+        proof: E::G1,
+        challenge: E::ScalarField,
+    ) -> PyResult<bool> {
+        if commitments.len() != values.len() {
+            return Err(value_error(
+                "the number of commitments and values must be equal",
+            ));
+        }
+        let challenge_powers: Vec<E::ScalarField> =
+            std::iter::successors(Some(E::ScalarField::one()), |power| {
+                Some(*power * challenge)
+            })
+            .take(commitments.len())
+            .collect();
+        let commitments = E::G1::normalize_batch(commitments);
+        let commitment = E::G1::msm_unchecked(&commitments, &challenge_powers);
+        let value = values
+            .iter()
+            .zip(&challenge_powers)
+            .map(|(v, c)| *v * c)
+            .sum();
+        Ok(self.verify(commitment, point, value, proof))
+    }
+
+    /// Returns the evaluations of `polynomial` at each of `points` and a single proof of
+    /// all of them, which commits to the quotient of `polynomial` by the vanishing
+    /// polynomial of `points`.
+    pub fn multi_open(
+        &self,
+        polynomial: &Poly<E::ScalarField>,
+        points: &[E::ScalarField],
+    ) -> PyResult<(Vec<E::ScalarField>, E::G1)> {
+        let polynomial = DensePolynomial::from(polynomial.clone());
+        self.check_degree(polynomial.degree())?;
+        let values = points
+            .iter()
+            .map(|point| polynomial.evaluate(point))
+            .collect();
+        let vanishing = vanishing_polynomial(points);
+        let (quotient, _) = DenseOrSparsePolynomial::from(polynomial)
+            .divide_with_q_and_r(&vanishing.into())
+            .expect("the vanishing polynomial is non-zero");
+        Ok((values, self.commit_coeffs(&quotient.coeffs)?))
+    }
+
+    /// Checks `e(commitment - [I(tau)], H) = e(proof, [Z(tau)])`, where `I` interpolates
+    /// `values` at `points` and `Z` is the vanishing polynomial of `points`.
+    pub fn multi_verify(
+        &self,
+        commitment: E::G1,
+        points: &[E::ScalarField],
+        values: &[E::ScalarField],
+        proof: E::G1,
+    ) -> PyResult<bool> {
+        if points.len() != values.len() {
+            return Err(value_error("the number of points and values must be equal"));
+        }
+        if points.len() >= self.powers_of_h.len() {
+            return Err(value_error(format!(
+                "at most {} points can be opened at once",
+                self.powers_of_h.len() - 1
+            )));
+        }
+        let interpolation = interpolate(points, values)
+            .ok_or_else(|| value_error("the points must be distinct"))?;
+        let vanishing = vanishing_polynomial(points);
+        let interpolation_commitment = self.commit_coeffs(&interpolation.coeffs)?;
+        let vanishing_commitment = E::G2::msm_unchecked(&self.powers_of_h, &vanishing.coeffs);
+        Ok(E::multi_pairing(
+            [
+                (commitment - interpolation_commitment).into_affine(),
+                (-proof).into_affine(),
+            ],
+            [self.powers_of_h[0], vanishing_commitment.into_affine()],
+        )
+        .is_zero())
+    }
+}
+
+#[macro_export]
+macro_rules! monomorphize_kzg {
+    ($struct: ident, $pairing: ty, $g1: ident, $g2: ident, $scalar: ident, $poly: ident, $srs: ident) => {
+        /// The KZG10 polynomial commitment scheme.
+        #[allow(clippy::upper_case_acronyms)]
+        #[pyo3::pyclass]
+        pub struct $struct($crate::kzg::Kzg<$pairing>);
+
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Generates a setup supporting polynomials of degree up to `max_degree` from
+            /// the secret `tau`. This is only meant for testing.
+            #[staticmethod]
+            fn setup(py: pyo3::Python, max_degree: usize, tau: $scalar) -> Self {
+                py.allow_threads(|| Self($crate::kzg::Kzg::setup(max_degree, tau.0)))
+            }
+
+            /// Constructs a setup from the powers of tau in G1 and G2.
+            #[staticmethod]
+            fn load(g1_powers: Vec<$g1>, g2_powers: Vec<$g2>) -> pyo3::PyResult<Self> {
+                $crate::kzg::Kzg::new(
+                    g1_powers.iter().map(|p| p.0.to_affine()).collect(),
+                    g2_powers.iter().map(|p| p.0.to_affine()).collect(),
+                )
+                .map(Self)
+            }
+
+            /// Constructs a setup from a structured reference string.
+            #[staticmethod]
+            fn from_srs(srs: pyo3::PyRef<$srs>) -> pyo3::PyResult<Self> {
+                $crate::kzg::Kzg::new(srs.0.g1_powers.clone(), srs.0.g2_powers.clone()).map(Self)
+            }
+
+            /// Returns the maximum degree of the polynomials that can be committed to.
+            fn max_degree(&self) -> usize {
+                self.0.max_degree()
+            }
+
+            /// Commits to `polynomial`.
+            fn commit(&self, py: pyo3::Python, polynomial: $poly) -> pyo3::PyResult<$g1> {
+                py.allow_threads(|| self.0.commit(&polynomial.0))
+                    .map(|c| $g1($crate::point::Point::Point(c)))
+            }
+
+            /// Returns the evaluation of `polynomial` at `point` and a proof of it.
+            fn open(
+                &self,
+                py: pyo3::Python,
+                polynomial: $poly,
+                point: $scalar,
+            ) -> pyo3::PyResult<($scalar, $g1)> {
+                py.allow_threads(|| self.0.open(&polynomial.0, point.0))
+                    .map(|(value, proof)| ($scalar(value), $g1($crate::point::Point::Point(proof))))
+            }
+
+            /// Checks that the polynomial committed to by `commitment` evaluates to `value`
+            /// at `point`.
+            fn verify(
+                &self,
+                py: pyo3::Python,
+                commitment: $g1,
+                point: $scalar,
+                value: $scalar,
+                proof: $g1,
+            ) -> bool {
+                py.allow_threads(|| {
+                    self.0.verify(
+                        commitment.0.to_group(),
+                        point.0,
+                        value.0,
+                        proof.0.to_group(),
+                    )
+                })
+            }
+
+            /// Opens all of `polynomials` at `point` with a single proof. The same
+            /// `challenge` must be used for verification, and should be derived from the
+            /// commitments, e.g. with a Fiat-Shamir transform.
+            fn batch_open(
+                &self,
+                py: pyo3::Python,
+                polynomials: Vec<$poly>,
+                point: $scalar,
+                challenge: $scalar,
+            ) -> pyo3::PyResult<(Vec<$scalar>, $g1)> {
+                let polynomials: Vec<_> = polynomials.into_iter().map(|p| p.0).collect();
+                py.allow_threads(|| self.0.batch_open(&polynomials, point.0, challenge.0))
+                    .map(|(values, proof)| {
+                        (
+                            values.into_iter().map($scalar).collect(),
+                            $g1($crate::point::Point::Point(proof)),
+                        )
+                    })
+            }
+
+            /// Checks a proof produced by `batch_open`.
+            fn batch_verify(
+                &self,
+                py: pyo3::Python,
+                commitments: Vec<$g1>,
+                point: $scalar,
+                values: Vec<$scalar>,
+                proof: $g1,
+                challenge: $scalar,
+            ) -> pyo3::PyResult<bool> {
+                let commitments: Vec<_> = commitments.iter().map(|c| c.0.to_group()).collect();
+                let values: Vec<_> = values.iter().map(|v| v.0).collect();
+                py.allow_threads(|| {
+                    self.0.batch_verify(
+                        &commitments,
+                        point.0,
+                        &values,
+                        proof.0.to_group(),
+                        challenge.0,
+                    )
+                })
+            }
+
+            /// Returns the evaluations of `polynomial` at each of `points` and a single
+            /// proof of all of them.
+            fn multi_open(
+                &self,
+                py: pyo3::Python,
+                polynomial: $poly,
+                points: Vec<$scalar>,
+            ) -> pyo3::PyResult<(Vec<$scalar>, $g1)> {
+                let points: Vec<_> = points.iter().map(|p| p.0).collect();
+                py.allow_threads(|| self.0.multi_open(&polynomial.0, &points))
+                    .map(|(values, proof)| {
+                        (
+                            values.into_iter().map($scalar).collect(),
+                            $g1($crate::point::Point::Point(proof)),
+                        )
+                    })
+            }
+
+            /// Checks a proof produced by `multi_open`. The setup must contain more powers
+            /// of tau in G2 than there are points.
+            fn multi_verify(
+                &self,
+                py: pyo3::Python,
+                commitment: $g1,
+                points: Vec<$scalar>,
+                values: Vec<$scalar>,
+                proof: $g1,
+            ) -> pyo3::PyResult<bool> {
+                let points: Vec<_> = points.iter().map(|p| p.0).collect();
+                let values: Vec<_> = values.iter().map(|v| v.0).collect();
+                py.allow_threads(|| {
+                    self.0.multi_verify(
+                        commitment.0.to_group(),
+                        &points,
+                        &values,
+                        proof.0.to_group(),
+                    )
+                })
+            }
+        }
+    };
+}
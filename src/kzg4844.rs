@@ -0,0 +1,517 @@
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use pyo3::{exceptions, prelude::*, types::PyBytes};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+use crate::utils::{serialisation_error_to_py_err, value_error};
+
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+pub const BYTES_PER_COMMITMENT: usize = 48;
+pub const BYTES_PER_PROOF: usize = 48;
+
+/// The number of G2 powers of tau in the trusted setup.
+pub const NUM_G2_POINTS: usize = 65;
+
+const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8] = b"FSBLOBVERIFY_V1_";
+const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &[u8] = b"RCKZGBATCH___V1_";
+
+const G1_POINT_AT_INFINITY: [u8; BYTES_PER_COMMITMENT] = {
+    let mut bytes = [0u8; BYTES_PER_COMMITMENT];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+pub(crate) fn check_length(bytes: &[u8], expected: usize, name: &str) -> PyResult<()> {
+    if bytes.len() != expected {
+        return Err(value_error(format!(
+            "{name} must be {expected} bytes long, got {}",
+            bytes.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Permutes `values` by reversing the bits of each index. The length of `values`
+/// must be a power of two.
+pub fn bit_reversal_permutation<T: Copy>(values: &[T]) -> Vec<T> {
+    let n = values.len();
+    assert!(n.is_power_of_two());
+    let log_n = n.trailing_zeros();
+    (0..n)
+        .map(|i| {
+            values[i
+                .reverse_bits()
+                .checked_shr(usize::BITS - log_n)
+                .unwrap_or(0)]
+        })
+        .collect()
+}
+
+/// Computes the `n`-th roots of unity in bit-reversed order.
+pub fn roots_of_unity_brp(n: usize) -> Vec<Fr> {
+    let domain = Radix2EvaluationDomain::<Fr>::new(n).unwrap();
+    bit_reversal_permutation(&domain.elements().collect::<Vec<_>>())
+}
+
+/// Converts a big-endian encoded field element, rejecting non-canonical encodings.
+pub fn bytes_to_bls_field(bytes: &[u8]) -> PyResult<Fr> {
+    check_length(bytes, BYTES_PER_FIELD_ELEMENT, "field element")?;
+    let mut le_bytes = bytes.to_vec();
+    le_bytes.reverse();
+    Fr::deserialize_compressed(&le_bytes[..])
+        .map_err(|_| value_error("field element is not canonical"))
+}
+
+pub fn bls_field_to_bytes(elem: &Fr) -> [u8; BYTES_PER_FIELD_ELEMENT] {
+    let mut bytes = [0u8; BYTES_PER_FIELD_ELEMENT];
+    elem.serialize_compressed(&mut bytes[..]).unwrap();
+    bytes.reverse();
+    bytes
+}
+
+pub fn hash_to_bls_field(data: &[u8]) -> Fr {
+    Fr::from_be_bytes_mod_order(&Sha256::digest(data))
+}
+
+/// Deserializes a G1 point, accepting the point at infinity only in its canonical encoding.
+pub fn validate_kzg_g1(bytes: &[u8]) -> PyResult<G1Affine> {
+    check_length(bytes, BYTES_PER_COMMITMENT, "G1 point")?;
+    if bytes == G1_POINT_AT_INFINITY {
+        return Ok(G1Affine::zero());
+    }
+    // The infinity flag is only valid in the canonical encoding handled above.
+    if bytes[0] & 0x40 != 0 {
+        return Err(value_error("invalid encoding of the point at infinity"));
+    }
+    G1Affine::deserialize_compressed(bytes).map_err(serialisation_error_to_py_err)
+}
+
+pub fn g1_to_bytes(point: &G1Affine) -> [u8; BYTES_PER_COMMITMENT] {
+    let mut bytes = [0u8; BYTES_PER_COMMITMENT];
+    point.serialize_compressed(&mut bytes[..]).unwrap();
+    bytes
+}
+
+pub fn blob_to_polynomial(blob: &[u8]) -> PyResult<Vec<Fr>> {
+    check_length(blob, BYTES_PER_BLOB, "blob")?;
+    blob.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(bytes_to_bls_field)
+        .collect()
+}
+
+fn compute_challenge(blob: &[u8], commitment: &[u8]) -> Fr {
+    let data = [
+        FIAT_SHAMIR_PROTOCOL_DOMAIN,
+        &(FIELD_ELEMENTS_PER_BLOB as u128).to_be_bytes(),
+        blob,
+        commitment,
+    ]
+    .concat();
+    hash_to_bls_field(&data)
+}
+
+/// Evaluates a polynomial given by its evaluations over `roots` (in bit-reversed order)
+/// at `z`, using the barycentric formula.
+pub fn evaluate_polynomial_in_evaluation_form(polynomial: &[Fr], roots: &[Fr], z: Fr) -> Fr {
+    if let Some(i) = roots.iter().position(|root| *root == z) {
+        return polynomial[i];
+    }
+    let mut denominators: Vec<Fr> = roots.iter().map(|root| z - root).collect();
+    ark_ff::batch_inversion(&mut denominators);
+    let result: Fr = polynomial
+        .iter()
+        .zip(roots)
+        .zip(denominators)
+        .map(|((p, root), inv)| *p * root * inv)
+        .sum();
+    let width = Fr::from(roots.len() as u64);
+    result * (z.pow([roots.len() as u64]) - Fr::one()) * width.inverse().unwrap()
+}
+
+/// Computes the evaluation at the root `z` of the quotient of `polynomial - y` by `X - z`.
+fn compute_quotient_eval_within_domain(z: Fr, polynomial: &[Fr], roots: &[Fr], y: Fr) -> Fr {
+    polynomial
+        .iter()
+        .zip(roots)
+        .filter(|(_, root)| **root != z)
+        .map(|(p, root)| (*p - y) * root / (z * (z - root)))
+        .sum()
+}
+
+/// A trusted setup for EIP-4844, with the Lagrange basis of G1 stored in bit-reversed order.
+/// The monomial basis of G1 is optional, and only needed for the EIP-7594 cell API.
+#[pyclass]
+pub struct TrustedSetup {
+    pub(crate) g1_lagrange_brp: Vec<G1Affine>,
+    pub(crate) g1_monomial: Vec<G1Affine>,
+    pub(crate) g2_monomial: Vec<G2Affine>,
+    pub(crate) roots_of_unity_brp: Vec<Fr>,
+    pub(crate) fk20_table: OnceLock<Vec<Vec<G1Affine>>>,
+}
+
+pub(crate) fn parse_points<P: CanonicalDeserialize + Send>(
+    hex_points: &[&str],
+) -> PyResult<Vec<P>> {
+    hex_points
+        .par_iter()
+        .map(|hex_point| {
+            let bytes = hex::decode(hex_point.trim_start_matches("0x"))
+                .map_err(|err| value_error(format!("invalid hex in trusted setup: {err}")))?;
+            P::deserialize_compressed(&bytes[..]).map_err(serialisation_error_to_py_err)
+        })
+        .collect()
+}
+
+impl TrustedSetup {
+    pub fn new(
+        g1_lagrange: Vec<G1Affine>,
+        g2_monomial: Vec<G2Affine>,
+        g1_monomial: Vec<G1Affine>,
+    ) -> PyResult<Self> {
+        if g1_lagrange.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(value_error(format!(
+                "expected {FIELD_ELEMENTS_PER_BLOB} G1 points in the trusted setup, got {}",
+                g1_lagrange.len()
+            )));
+        }
+        if g2_monomial.len() != NUM_G2_POINTS {
+            return Err(value_error(format!(
+                "expected {NUM_G2_POINTS} G2 points in the trusted setup, got {}",
+                g2_monomial.len()
+            )));
+        }
+        if !g1_monomial.is_empty() && g1_monomial.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(value_error(format!(
+                "expected {FIELD_ELEMENTS_PER_BLOB} monomial G1 points in the trusted setup, got {}",
+                g1_monomial.len()
+            )));
+        }
+        Ok(Self {
+            g1_lagrange_brp: bit_reversal_permutation(&g1_lagrange),
+            g1_monomial,
+            g2_monomial,
+            roots_of_unity_brp: roots_of_unity_brp(FIELD_ELEMENTS_PER_BLOB),
+            fk20_table: OnceLock::new(),
+        })
+    }
+
+    fn g1_lincomb(&self, scalars: &[Fr]) -> G1Affine {
+        G1Projective::msm_unchecked(&self.g1_lagrange_brp, scalars).into_affine()
+    }
+
+    pub fn compute_kzg_proof_impl(&self, polynomial: &[Fr], z: Fr) -> (G1Affine, Fr) {
+        let roots = &self.roots_of_unity_brp;
+        let y = evaluate_polynomial_in_evaluation_form(polynomial, roots, z);
+
+        let mut denominators: Vec<Fr> = roots.iter().map(|root| *root - z).collect();
+        ark_ff::batch_inversion(&mut denominators);
+        let quotient: Vec<Fr> = polynomial
+            .par_iter()
+            .zip(roots)
+            .zip(denominators)
+            .map(|((p, root), inv)| {
+                if *root == z {
+                    compute_quotient_eval_within_domain(*root, polynomial, roots, y)
+                } else {
+                    (*p - y) * inv
+                }
+            })
+            .collect();
+        (self.g1_lincomb(&quotient), y)
+    }
+
+    pub fn verify_kzg_proof_impl(
+        &self,
+        commitment: G1Affine,
+        z: Fr,
+        y: Fr,
+        proof: G1Affine,
+    ) -> bool {
+        let x_minus_z = self.g2_monomial[1].into_group() - self.g2_monomial[0] * z;
+        let p_minus_y = commitment.into_group() - G1Affine::generator() * y;
+        Bls12_381::multi_pairing(
+            [p_minus_y.into_affine(), proof],
+            [-G2Affine::generator(), x_minus_z.into_affine()],
+        )
+        .is_zero()
+    }
+
+    fn verify_kzg_proof_batch(
+        &self,
+        commitments: &[(&[u8], G1Affine)],
+        zs: &[Fr],
+        ys: &[Fr],
+        proofs: &[(&[u8], G1Affine)],
+    ) -> bool {
+        let mut data = [
+            RANDOM_CHALLENGE_KZG_BATCH_DOMAIN,
+            &(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes(),
+            &(commitments.len() as u64).to_be_bytes(),
+        ]
+        .concat();
+        for (((commitment, z), y), proof) in commitments.iter().zip(zs).zip(ys).zip(proofs) {
+            data.extend_from_slice(commitment.0);
+            data.extend_from_slice(&bls_field_to_bytes(z));
+            data.extend_from_slice(&bls_field_to_bytes(y));
+            data.extend_from_slice(proof.0);
+        }
+        let r = hash_to_bls_field(&data);
+        let r_powers: Vec<Fr> = std::iter::successors(Some(Fr::one()), |power| Some(*power * r))
+            .take(commitments.len())
+            .collect();
+
+        let proofs: Vec<G1Affine> = proofs.iter().map(|proof| proof.1).collect();
+        let proof_lincomb = G1Projective::msm_unchecked(&proofs, &r_powers);
+        let z_r_powers: Vec<Fr> = zs.iter().zip(&r_powers).map(|(z, r)| *z * r).collect();
+        let proof_z_lincomb = G1Projective::msm_unchecked(&proofs, &z_r_powers);
+        let c_minus_ys: Vec<G1Projective> = commitments
+            .iter()
+            .zip(ys)
+            .map(|(commitment, y)| commitment.1.into_group() - G1Affine::generator() * y)
+            .collect();
+        let c_minus_ys = G1Projective::normalize_batch(&c_minus_ys);
+        let c_minus_y_lincomb = G1Projective::msm_unchecked(&c_minus_ys, &r_powers);
+
+        Bls12_381::multi_pairing(
+            [
+                proof_lincomb.into_affine(),
+                (c_minus_y_lincomb + proof_z_lincomb).into_affine(),
+            ],
+            [-self.g2_monomial[1], G2Affine::generator()],
+        )
+        .is_zero()
+    }
+}
+
+#[pymethods]
+impl TrustedSetup {
+    /// Loads a trusted setup from a file in either the text format used by
+    /// `c-kzg-4844` or the JSON format used by the consensus specs.
+    #[staticmethod]
+    fn load(py: Python, path: &str) -> PyResult<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| exceptions::PyIOError::new_err(err.to_string()))?;
+        if contents.trim_start().starts_with('{') {
+            Self::from_json(py, &contents)
+        } else {
+            Self::from_text(py, &contents)
+        }
+    }
+
+    /// Parses a trusted setup in the JSON format, with `g1_lagrange`, `g2_monomial` and
+    /// optionally `g1_monomial` keys holding hex-encoded compressed points.
+    #[staticmethod]
+    fn from_json(py: Python, json: &str) -> PyResult<Self> {
+        let json: serde_json::Value = serde_json::from_str(json)
+            .map_err(|err| value_error(format!("invalid trusted setup JSON: {err}")))?;
+        let hex_points = |key: &str| -> PyResult<Vec<&str>> {
+            match json.get(key) {
+                None => Ok(Vec::new()),
+                Some(points) => points
+                    .as_array()
+                    .and_then(|points| points.iter().map(|p| p.as_str()).collect())
+                    .ok_or_else(|| value_error(format!("`{key}` must be a list of strings"))),
+            }
+        };
+        let g1_lagrange = hex_points("g1_lagrange")?;
+        let g2_monomial = hex_points("g2_monomial")?;
+        let g1_monomial = hex_points("g1_monomial")?;
+        py.allow_threads(|| {
+            Self::new(
+                parse_points(&g1_lagrange)?,
+                parse_points(&g2_monomial)?,
+                parse_points(&g1_monomial)?,
+            )
+        })
+    }
+
+    /// Parses a trusted setup in the text format: the number of G1 points, the number
+    /// of G2 points, the Lagrange G1 points and the monomial G2 points, all separated by
+    /// whitespace, optionally followed by the monomial G1 points.
+    #[staticmethod]
+    fn from_text(py: Python, text: &str) -> PyResult<Self> {
+        let mut tokens = text.split_whitespace();
+        let mut read_count = || -> PyResult<usize> {
+            tokens
+                .next()
+                .and_then(|token| token.parse().ok())
+                .ok_or_else(|| value_error("invalid trusted setup header"))
+        };
+        let num_g1 = read_count()?;
+        let num_g2 = read_count()?;
+
+        let tokens: Vec<&str> = tokens.collect();
+        let count_error = || {
+            value_error(format!(
+                "expected {num_g1} G1 points and {num_g2} G2 points in the trusted setup"
+            ))
+        };
+        let num_points = num_g1.checked_add(num_g2).ok_or_else(count_error)?;
+        let num_points_with_monomial = num_points.checked_add(num_g1).ok_or_else(count_error)?;
+        if tokens.len() != num_points && tokens.len() != num_points_with_monomial {
+            return Err(count_error());
+        }
+        let g1_lagrange = &tokens[..num_g1];
+        let g2_monomial = &tokens[num_g1..num_points];
+        let g1_monomial = &tokens[num_points..];
+        py.allow_threads(|| {
+            Self::new(
+                parse_points(g1_lagrange)?,
+                parse_points(g2_monomial)?,
+                parse_points(g1_monomial)?,
+            )
+        })
+    }
+
+    /// Computes the KZG commitment of a blob.
+    fn blob_to_kzg_commitment<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let commitment = py.allow_threads(|| {
+            let polynomial = blob_to_polynomial(blob)?;
+            Ok::<_, PyErr>(g1_to_bytes(&self.g1_lincomb(&polynomial)))
+        })?;
+        Ok(PyBytes::new_bound(py, &commitment))
+    }
+
+    /// Computes the KZG proof for the evaluation of the blob's polynomial at `z`.
+    /// Returns the proof and the evaluation `y`.
+    fn compute_kzg_proof<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+        z_bytes: &[u8],
+    ) -> PyResult<(Bound<'py, PyBytes>, Bound<'py, PyBytes>)> {
+        let (proof, y) = py.allow_threads(|| {
+            let polynomial = blob_to_polynomial(blob)?;
+            let z = bytes_to_bls_field(z_bytes)?;
+            let (proof, y) = self.compute_kzg_proof_impl(&polynomial, z);
+            Ok::<_, PyErr>((g1_to_bytes(&proof), bls_field_to_bytes(&y)))
+        })?;
+        Ok((PyBytes::new_bound(py, &proof), PyBytes::new_bound(py, &y)))
+    }
+
+    /// Computes the KZG proof for the evaluation of the blob's polynomial at the
+    /// Fiat-Shamir challenge derived from the blob and its commitment.
+    fn compute_blob_kzg_proof<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+        commitment_bytes: &[u8],
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let proof = py.allow_threads(|| {
+            let polynomial = blob_to_polynomial(blob)?;
+            let _ = validate_kzg_g1(commitment_bytes)?;
+            let challenge = compute_challenge(blob, commitment_bytes);
+            let (proof, _) = self.compute_kzg_proof_impl(&polynomial, challenge);
+            Ok::<_, PyErr>(g1_to_bytes(&proof))
+        })?;
+        Ok(PyBytes::new_bound(py, &proof))
+    }
+
+    /// Checks a KZG proof that the polynomial committed to by `commitment` evaluates
+    /// to `y` at `z`.
+    fn verify_kzg_proof(
+        &self,
+        py: Python,
+        commitment_bytes: &[u8],
+        z_bytes: &[u8],
+        y_bytes: &[u8],
+        proof_bytes: &[u8],
+    ) -> PyResult<bool> {
+        py.allow_threads(|| {
+            let commitment = validate_kzg_g1(commitment_bytes)?;
+            let z = bytes_to_bls_field(z_bytes)?;
+            let y = bytes_to_bls_field(y_bytes)?;
+            let proof = validate_kzg_g1(proof_bytes)?;
+            Ok(self.verify_kzg_proof_impl(commitment, z, y, proof))
+        })
+    }
+
+    /// Checks a KZG proof produced by `compute_blob_kzg_proof`.
+    fn verify_blob_kzg_proof(
+        &self,
+        py: Python,
+        blob: &[u8],
+        commitment_bytes: &[u8],
+        proof_bytes: &[u8],
+    ) -> PyResult<bool> {
+        py.allow_threads(|| {
+            let commitment = validate_kzg_g1(commitment_bytes)?;
+            let polynomial = blob_to_polynomial(blob)?;
+            let challenge = compute_challenge(blob, commitment_bytes);
+            let y = evaluate_polynomial_in_evaluation_form(
+                &polynomial,
+                &self.roots_of_unity_brp,
+                challenge,
+            );
+            let proof = validate_kzg_g1(proof_bytes)?;
+            Ok(self.verify_kzg_proof_impl(commitment, challenge, y, proof))
+        })
+    }
+
+    /// Checks a batch of KZG proofs produced by `compute_blob_kzg_proof`, using a
+    /// random linear combination to perform a single pairing check.
+    fn verify_blob_kzg_proof_batch<'py>(
+        &self,
+        py: Python<'py>,
+        blobs: Vec<Bound<'py, PyBytes>>,
+        commitments_bytes: Vec<Bound<'py, PyBytes>>,
+        proofs_bytes: Vec<Bound<'py, PyBytes>>,
+    ) -> PyResult<bool> {
+        if blobs.len() != commitments_bytes.len() || blobs.len() != proofs_bytes.len() {
+            return Err(value_error(
+                "the number of blobs, commitments and proofs must be equal",
+            ));
+        }
+        let blobs: Vec<&[u8]> = blobs.iter().map(|blob| blob.as_bytes()).collect();
+        let commitments_bytes: Vec<&[u8]> =
+            commitments_bytes.iter().map(|c| c.as_bytes()).collect();
+        let proofs_bytes: Vec<&[u8]> = proofs_bytes.iter().map(|proof| proof.as_bytes()).collect();
+        py.allow_threads(|| {
+            let checked = blobs
+                .par_iter()
+                .zip(&commitments_bytes)
+                .zip(&proofs_bytes)
+                .map(|((blob, commitment_bytes), proof_bytes)| {
+                    let commitment = validate_kzg_g1(commitment_bytes)?;
+                    let polynomial = blob_to_polynomial(blob)?;
+                    let challenge = compute_challenge(blob, commitment_bytes);
+                    let y = evaluate_polynomial_in_evaluation_form(
+                        &polynomial,
+                        &self.roots_of_unity_brp,
+                        challenge,
+                    );
+                    let proof = validate_kzg_g1(proof_bytes)?;
+                    Ok((
+                        (*commitment_bytes, commitment),
+                        challenge,
+                        y,
+                        (*proof_bytes, proof),
+                    ))
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+
+            let mut commitments = Vec::with_capacity(checked.len());
+            let mut zs = Vec::with_capacity(checked.len());
+            let mut ys = Vec::with_capacity(checked.len());
+            let mut proofs = Vec::with_capacity(checked.len());
+            for (commitment, z, y, proof) in checked {
+                commitments.push(commitment);
+                zs.push(z);
+                ys.push(y);
+                proofs.push(proof);
+            }
+            Ok(self.verify_kzg_proof_batch(&commitments, &zs, &ys, &proofs))
+        })
+    }
+}
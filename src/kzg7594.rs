@@ -0,0 +1,471 @@
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{FftField, Field, One, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use pyo3::{prelude::*, types::PyBytes};
+use rayon::prelude::*;
+
+use crate::kzg::vanishing_polynomial;
+use crate::kzg4844::{
+    bit_reversal_permutation, blob_to_polynomial, bls_field_to_bytes, bytes_to_bls_field,
+    check_length, g1_to_bytes, hash_to_bls_field, validate_kzg_g1, TrustedSetup,
+    BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF, FIELD_ELEMENTS_PER_BLOB,
+};
+use crate::utils::value_error;
+
+pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = 2 * FIELD_ELEMENTS_PER_BLOB;
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT;
+pub const CELLS_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB / FIELD_ELEMENTS_PER_CELL;
+
+/// The number of cells that the original blob spans.
+const CELLS_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB / FIELD_ELEMENTS_PER_CELL;
+
+type Cell = Vec<u8>;
+
+const RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN: &[u8] = b"RCKZGCBATCH__V1_";
+
+fn domain(size: usize) -> Radix2EvaluationDomain<Fr> {
+    Radix2EvaluationDomain::new(size).unwrap()
+}
+
+fn reverse_bits(index: usize, n: usize) -> usize {
+    index
+        .reverse_bits()
+        .checked_shr(usize::BITS - n.trailing_zeros())
+        .unwrap_or(0)
+}
+
+fn cell_to_coset_evals(cell: &[u8]) -> PyResult<Vec<Fr>> {
+    check_length(cell, BYTES_PER_CELL, "cell")?;
+    cell.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(bytes_to_bls_field)
+        .collect()
+}
+
+fn coset_evals_to_cell(coset_evals: &[Fr]) -> Vec<u8> {
+    coset_evals.iter().flat_map(bls_field_to_bytes).collect()
+}
+
+fn check_cell_index(cell_index: usize) -> PyResult<()> {
+    if cell_index >= CELLS_PER_EXT_BLOB {
+        return Err(value_error(format!(
+            "cell index must be less than {CELLS_PER_EXT_BLOB}, got {cell_index}"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the shift `h` of the coset `h * <w>` that the evaluations of cell
+/// `cell_index` lie on, where `w` is a primitive `FIELD_ELEMENTS_PER_CELL`-th root of unity.
+fn coset_shift_for_cell(cell_index: usize) -> Fr {
+    domain(FIELD_ELEMENTS_PER_EXT_BLOB).element(reverse_bits(cell_index, CELLS_PER_EXT_BLOB))
+}
+
+/// Converts a polynomial in evaluation form over the bit-reversed roots of unity into
+/// coefficient form.
+fn polynomial_eval_to_coeff(polynomial: &[Fr]) -> Vec<Fr> {
+    domain(FIELD_ELEMENTS_PER_BLOB).ifft(&bit_reversal_permutation(polynomial))
+}
+
+/// Evaluates a polynomial over the extended domain and splits the bit-reversed
+/// evaluations into cells.
+fn compute_cosets_evals(polynomial_coeff: &[Fr]) -> Vec<Vec<Fr>> {
+    let extended_data = domain(FIELD_ELEMENTS_PER_EXT_BLOB).fft(polynomial_coeff);
+    bit_reversal_permutation(&extended_data)
+        .chunks(FIELD_ELEMENTS_PER_CELL)
+        .map(|coset_evals| coset_evals.to_vec())
+        .collect()
+}
+
+/// Recovers the coefficients of the blob's polynomial from at least half of the cells
+/// of the extended blob, by dividing out the polynomial vanishing on the missing cells.
+fn recover_polynomial_coeff(cell_indices: &[usize], cosets_evals: &[Vec<Fr>]) -> Vec<Fr> {
+    let mut extended_evaluation_rbo = vec![Fr::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (cell_index, coset_evals) in cell_indices.iter().zip(cosets_evals) {
+        let start = cell_index * FIELD_ELEMENTS_PER_CELL;
+        extended_evaluation_rbo[start..start + FIELD_ELEMENTS_PER_CELL]
+            .copy_from_slice(coset_evals);
+    }
+    let extended_evaluation = bit_reversal_permutation(&extended_evaluation_rbo);
+
+    // The vanishing polynomial of the missing cells over the domain of size
+    // `CELLS_PER_EXT_BLOB`, extended to the full domain by substituting `X^FIELD_ELEMENTS_PER_CELL`.
+    let cells_domain = domain(CELLS_PER_EXT_BLOB);
+    let missing_roots: Vec<Fr> = (0..CELLS_PER_EXT_BLOB)
+        .filter(|cell_index| !cell_indices.contains(cell_index))
+        .map(|cell_index| cells_domain.element(reverse_bits(cell_index, CELLS_PER_EXT_BLOB)))
+        .collect();
+    let mut zero_poly_coeff = vec![Fr::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (i, coeff) in vanishing_polynomial(&missing_roots)
+        .coeffs
+        .into_iter()
+        .enumerate()
+    {
+        zero_poly_coeff[i * FIELD_ELEMENTS_PER_CELL] = coeff;
+    }
+
+    let extended_domain = domain(FIELD_ELEMENTS_PER_EXT_BLOB);
+    let zero_poly_eval = extended_domain.fft(&zero_poly_coeff);
+    let extended_evaluation_times_zero: Vec<Fr> = zero_poly_eval
+        .iter()
+        .zip(&extended_evaluation)
+        .map(|(z, e)| *z * e)
+        .collect();
+    let extended_evaluation_times_zero_coeffs =
+        extended_domain.ifft(&extended_evaluation_times_zero);
+
+    // Divide on a coset of the domain, where the vanishing polynomial has no roots.
+    let coset = extended_domain.get_coset(Fr::GENERATOR).unwrap();
+    let extended_evaluations_over_coset = coset.fft(&extended_evaluation_times_zero_coeffs);
+    let mut zero_poly_over_coset = coset.fft(&zero_poly_coeff);
+    ark_ff::batch_inversion(&mut zero_poly_over_coset);
+    let reconstructed_poly_over_coset: Vec<Fr> = extended_evaluations_over_coset
+        .iter()
+        .zip(&zero_poly_over_coset)
+        .map(|(e, z_inv)| *e * z_inv)
+        .collect();
+
+    let mut reconstructed_poly_coeff = coset.ifft(&reconstructed_poly_over_coset);
+    reconstructed_poly_coeff.truncate(FIELD_ELEMENTS_PER_BLOB);
+    reconstructed_poly_coeff
+}
+
+impl TrustedSetup {
+    fn g1_monomial(&self) -> PyResult<&[G1Affine]> {
+        if self.g1_monomial.is_empty() {
+            return Err(value_error(
+                "the trusted setup does not contain the monomial G1 points",
+            ));
+        }
+        Ok(&self.g1_monomial)
+    }
+
+    /// Returns, for each of the `2 * CELLS_PER_BLOB` frequencies, the FFTs of the
+    /// strided monomial G1 points `[s^(FIELD_ELEMENTS_PER_CELL * a + b)]` for every
+    /// offset `b` within a cell. These only depend on the setup, so they are computed
+    /// once, on first use.
+    fn fk20_table(&self, g1_monomial: &[G1Affine]) -> &[Vec<G1Affine>] {
+        self.fk20_table.get_or_init(|| {
+            let domain = domain(CELLS_PER_EXT_BLOB);
+            let columns: Vec<Vec<G1Projective>> = (0..FIELD_ELEMENTS_PER_CELL)
+                .into_par_iter()
+                .map(|b| {
+                    let mut column: Vec<G1Projective> = (0..CELLS_PER_BLOB - 1)
+                        .map(|a| g1_monomial[FIELD_ELEMENTS_PER_CELL * a + b].into_group())
+                        .collect();
+                    domain.fft_in_place(&mut column);
+                    column
+                })
+                .collect();
+            (0..CELLS_PER_EXT_BLOB)
+                .into_par_iter()
+                .map(|e| {
+                    let row: Vec<G1Projective> = columns.iter().map(|column| column[e]).collect();
+                    G1Projective::normalize_batch(&row)
+                })
+                .collect()
+        })
+    }
+
+    /// Computes the proofs for all cells of the extended blob at once, using the FK20
+    /// algorithm. Returns the proofs in the same order as the cells.
+    ///
+    /// The proof for the cell on the coset with shift `h` commits to the quotient of the
+    /// polynomial by `X^l - h^l`, with `l = FIELD_ELEMENTS_PER_CELL`. Writing `f_j` for
+    /// the coefficients, that is `sum_m (h^l)^m H_m`, where
+    /// `H_m = sum_j f_(l(m+1)+j) [s^j]`. The `H_m` are computed as Toeplitz
+    /// matrix-vector products in the Fourier domain, and since the `h^l` range over the
+    /// `CELLS_PER_EXT_BLOB`-th roots of unity, all proofs are a single FFT of the `H_m`.
+    fn compute_cell_proofs(&self, polynomial_coeff: &[Fr]) -> PyResult<Vec<G1Affine>> {
+        let table = self.fk20_table(self.g1_monomial()?);
+        let domain = domain(CELLS_PER_EXT_BLOB);
+
+        let columns: Vec<Vec<Fr>> = (0..FIELD_ELEMENTS_PER_CELL)
+            .into_par_iter()
+            .map(|b| {
+                let mut column: Vec<Fr> = (0..CELLS_PER_BLOB)
+                    .map(|t| {
+                        polynomial_coeff[FIELD_ELEMENTS_PER_CELL * (CELLS_PER_BLOB - 1 - t) + b]
+                    })
+                    .collect();
+                domain.fft_in_place(&mut column);
+                column
+            })
+            .collect();
+        let mut convolution: Vec<G1Projective> = table
+            .par_iter()
+            .enumerate()
+            .map(|(e, points)| {
+                let scalars: Vec<Fr> = columns.iter().map(|column| column[e]).collect();
+                G1Projective::msm_unchecked(points, &scalars)
+            })
+            .collect();
+        domain.ifft_in_place(&mut convolution);
+
+        let mut h: Vec<G1Projective> = (0..CELLS_PER_BLOB - 1)
+            .map(|m| convolution[CELLS_PER_BLOB - 2 - m])
+            .collect();
+        domain.fft_in_place(&mut h);
+        Ok(bit_reversal_permutation(&G1Projective::normalize_batch(&h)))
+    }
+
+    fn compute_cells_and_kzg_proofs_impl(
+        &self,
+        polynomial_coeff: &[Fr],
+    ) -> PyResult<(Vec<Cell>, Vec<[u8; BYTES_PER_PROOF]>)> {
+        let proofs = self.compute_cell_proofs(polynomial_coeff)?;
+        let cells = compute_cosets_evals(polynomial_coeff)
+            .iter()
+            .map(|coset_evals| coset_evals_to_cell(coset_evals))
+            .collect();
+        Ok((cells, proofs.iter().map(g1_to_bytes).collect()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_cell_kzg_proof_batch_impl(
+        &self,
+        commitments: &[(&[u8], G1Affine)],
+        commitment_indices: &[usize],
+        cell_indices: &[usize],
+        cells: &[&[u8]],
+        cosets_evals: &[Vec<Fr>],
+        proofs: &[(&[u8], G1Affine)],
+    ) -> PyResult<bool> {
+        let g1_monomial = self.g1_monomial()?;
+        if cell_indices.is_empty() {
+            return Ok(true);
+        }
+
+        let mut data = [
+            RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN,
+            &(FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes(),
+            &(FIELD_ELEMENTS_PER_CELL as u64).to_be_bytes(),
+            &(commitments.len() as u64).to_be_bytes(),
+            &(cell_indices.len() as u64).to_be_bytes(),
+        ]
+        .concat();
+        for commitment in commitments {
+            data.extend_from_slice(commitment.0);
+        }
+        for (((commitment_index, cell_index), cell), proof) in commitment_indices
+            .iter()
+            .zip(cell_indices)
+            .zip(cells)
+            .zip(proofs)
+        {
+            data.extend_from_slice(&(*commitment_index as u64).to_be_bytes());
+            data.extend_from_slice(&(*cell_index as u64).to_be_bytes());
+            data.extend_from_slice(cell);
+            data.extend_from_slice(proof.0);
+        }
+        let r = hash_to_bls_field(&data);
+        let r_powers: Vec<Fr> = std::iter::successors(Some(Fr::one()), |power| Some(*power * r))
+            .take(cell_indices.len())
+            .collect();
+
+        // We check `e(sum_k r^k proofs[k], [s^l]) = e(RLC - RLI + RLP, [1])`, where
+        // `RLC = sum_k r^k commitments[k]`, `RLI = [sum_k r^k I_k(s)]` for the
+        // interpolation polynomial `I_k` of cell `k`, and `RLP = sum_k r^k h_k^l proofs[k]`.
+        let proofs: Vec<G1Affine> = proofs.iter().map(|proof| proof.1).collect();
+        let proof_lincomb = G1Projective::msm_unchecked(&proofs, &r_powers);
+
+        let mut weights = vec![Fr::zero(); commitments.len()];
+        for (commitment_index, r_power) in commitment_indices.iter().zip(&r_powers) {
+            weights[*commitment_index] += r_power;
+        }
+        let commitments: Vec<G1Affine> = commitments.iter().map(|c| c.1).collect();
+        let commitment_lincomb = G1Projective::msm_unchecked(&commitments, &weights);
+
+        // Cells with the same index share an interpolation domain, so their evaluations
+        // are combined before interpolating.
+        let mut aggregated_cosets_evals = vec![None; CELLS_PER_EXT_BLOB];
+        for ((cell_index, coset_evals), r_power) in
+            cell_indices.iter().zip(cosets_evals).zip(&r_powers)
+        {
+            let aggregated = aggregated_cosets_evals[*cell_index]
+                .get_or_insert_with(|| vec![Fr::zero(); FIELD_ELEMENTS_PER_CELL]);
+            for (aggregated, eval) in aggregated.iter_mut().zip(coset_evals) {
+                *aggregated += *r_power * eval;
+            }
+        }
+        let cell_domain = domain(FIELD_ELEMENTS_PER_CELL);
+        let mut interpolation_poly_coeff = vec![Fr::zero(); FIELD_ELEMENTS_PER_CELL];
+        for (cell_index, coset_evals) in aggregated_cosets_evals.iter().enumerate() {
+            let Some(coset_evals) = coset_evals else {
+                continue;
+            };
+            let shifted_coeff = cell_domain.ifft(&bit_reversal_permutation(coset_evals));
+            let shift_inv = coset_shift_for_cell(cell_index).inverse().unwrap();
+            let mut shift_inv_power = Fr::one();
+            for (coeff, shifted) in interpolation_poly_coeff.iter_mut().zip(shifted_coeff) {
+                *coeff += shifted * shift_inv_power;
+                shift_inv_power *= shift_inv;
+            }
+        }
+        let interpolation_commitment = G1Projective::msm_unchecked(
+            &g1_monomial[..FIELD_ELEMENTS_PER_CELL],
+            &interpolation_poly_coeff,
+        );
+
+        let weighted_r_powers: Vec<Fr> = cell_indices
+            .iter()
+            .zip(&r_powers)
+            .map(|(cell_index, r_power)| {
+                coset_shift_for_cell(*cell_index).pow([FIELD_ELEMENTS_PER_CELL as u64]) * r_power
+            })
+            .collect();
+        let weighted_proof_lincomb = G1Projective::msm_unchecked(&proofs, &weighted_r_powers);
+
+        let rl = commitment_lincomb - interpolation_commitment + weighted_proof_lincomb;
+        Ok(Bls12_381::multi_pairing(
+            [proof_lincomb.into_affine(), rl.into_affine()],
+            [
+                self.g2_monomial[FIELD_ELEMENTS_PER_CELL],
+                -self.g2_monomial[0],
+            ],
+        )
+        .is_zero())
+    }
+}
+
+fn to_py_bytes<'py, T: AsRef<[u8]>>(py: Python<'py>, values: &[T]) -> Vec<Bound<'py, PyBytes>> {
+    values
+        .iter()
+        .map(|value| PyBytes::new_bound(py, value.as_ref()))
+        .collect()
+}
+
+type CellsAndProofs<'py> = (Vec<Bound<'py, PyBytes>>, Vec<Bound<'py, PyBytes>>);
+
+/// The cell API of EIP-7594 (PeerDAS), which requires the monomial G1 points of the setup.
+#[pymethods]
+impl TrustedSetup {
+    /// Extends a blob to twice its size and splits it into `CELLS_PER_EXT_BLOB` cells.
+    fn compute_cells<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<Vec<Bound<'py, PyBytes>>> {
+        let cells = py.allow_threads(|| {
+            let polynomial_coeff = polynomial_eval_to_coeff(&blob_to_polynomial(blob)?);
+            Ok::<_, PyErr>(
+                compute_cosets_evals(&polynomial_coeff)
+                    .iter()
+                    .map(|coset_evals| coset_evals_to_cell(coset_evals))
+                    .collect::<Vec<_>>(),
+            )
+        })?;
+        Ok(to_py_bytes(py, &cells))
+    }
+
+    /// Extends a blob into cells, and computes the KZG multi-proof of each cell.
+    fn compute_cells_and_kzg_proofs<'py>(
+        &self,
+        py: Python<'py>,
+        blob: &[u8],
+    ) -> PyResult<CellsAndProofs<'py>> {
+        let (cells, proofs) = py.allow_threads(|| {
+            let polynomial_coeff = polynomial_eval_to_coeff(&blob_to_polynomial(blob)?);
+            self.compute_cells_and_kzg_proofs_impl(&polynomial_coeff)
+        })?;
+        Ok((to_py_bytes(py, &cells), to_py_bytes(py, &proofs)))
+    }
+
+    /// Recovers all cells and their proofs from at least half of the cells of an
+    /// extended blob.
+    fn recover_cells_and_kzg_proofs<'py>(
+        &self,
+        py: Python<'py>,
+        cell_indices: Vec<usize>,
+        cells: Vec<Bound<'py, PyBytes>>,
+    ) -> PyResult<CellsAndProofs<'py>> {
+        if cell_indices.len() != cells.len() {
+            return Err(value_error(
+                "the number of cell indices and cells must be equal",
+            ));
+        }
+        if cell_indices.len() < CELLS_PER_EXT_BLOB / 2 || cell_indices.len() > CELLS_PER_EXT_BLOB {
+            return Err(value_error(format!(
+                "between {} and {CELLS_PER_EXT_BLOB} cells are needed to recover a blob, got {}",
+                CELLS_PER_EXT_BLOB / 2,
+                cell_indices.len()
+            )));
+        }
+        let mut seen = [false; CELLS_PER_EXT_BLOB];
+        for cell_index in &cell_indices {
+            check_cell_index(*cell_index)?;
+            if std::mem::replace(&mut seen[*cell_index], true) {
+                return Err(value_error(format!("duplicate cell index {cell_index}")));
+            }
+        }
+        let cells: Vec<&[u8]> = cells.iter().map(|cell| cell.as_bytes()).collect();
+
+        let (cells, proofs) = py.allow_threads(|| {
+            let cosets_evals = cells
+                .par_iter()
+                .map(|cell| cell_to_coset_evals(cell))
+                .collect::<PyResult<Vec<_>>>()?;
+            let polynomial_coeff = recover_polynomial_coeff(&cell_indices, &cosets_evals);
+            self.compute_cells_and_kzg_proofs_impl(&polynomial_coeff)
+        })?;
+        Ok((to_py_bytes(py, &cells), to_py_bytes(py, &proofs)))
+    }
+
+    /// Checks a batch of cell proofs, where `cells[i]` is the cell at index
+    /// `cell_indices[i]` of the extended blob committed to by `commitments[i]`.
+    fn verify_cell_kzg_proof_batch<'py>(
+        &self,
+        py: Python<'py>,
+        commitments_bytes: Vec<Bound<'py, PyBytes>>,
+        cell_indices: Vec<usize>,
+        cells: Vec<Bound<'py, PyBytes>>,
+        proofs_bytes: Vec<Bound<'py, PyBytes>>,
+    ) -> PyResult<bool> {
+        let n = commitments_bytes.len();
+        if cell_indices.len() != n || cells.len() != n || proofs_bytes.len() != n {
+            return Err(value_error(
+                "the number of commitments, cell indices, cells and proofs must be equal",
+            ));
+        }
+        for cell_index in &cell_indices {
+            check_cell_index(*cell_index)?;
+        }
+        let commitments_bytes: Vec<&[u8]> =
+            commitments_bytes.iter().map(|c| c.as_bytes()).collect();
+        let cells: Vec<&[u8]> = cells.iter().map(|cell| cell.as_bytes()).collect();
+        let proofs_bytes: Vec<&[u8]> = proofs_bytes.iter().map(|proof| proof.as_bytes()).collect();
+
+        py.allow_threads(|| {
+            let mut commitments: Vec<(&[u8], G1Affine)> = Vec::new();
+            let mut commitment_indices = Vec::with_capacity(n);
+            for commitment_bytes in &commitments_bytes {
+                check_length(commitment_bytes, BYTES_PER_COMMITMENT, "commitment")?;
+                let index = match commitments.iter().position(|c| c.0 == *commitment_bytes) {
+                    Some(index) => index,
+                    None => {
+                        commitments.push((commitment_bytes, validate_kzg_g1(commitment_bytes)?));
+                        commitments.len() - 1
+                    }
+                };
+                commitment_indices.push(index);
+            }
+            let cosets_evals = cells
+                .par_iter()
+                .map(|cell| cell_to_coset_evals(cell))
+                .collect::<PyResult<Vec<_>>>()?;
+            let proofs = proofs_bytes
+                .iter()
+                .map(|proof_bytes| Ok((*proof_bytes, validate_kzg_g1(proof_bytes)?)))
+                .collect::<PyResult<Vec<_>>>()?;
+            self.verify_cell_kzg_proof_batch_impl(
+                &commitments,
+                &commitment_indices,
+                &cell_indices,
+                &cells,
+                &cosets_evals,
+                &proofs,
+            )
+        })
+    }
+}
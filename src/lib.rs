@@ -1,24 +1,100 @@
 mod wrapper;
 #[macro_use]
 mod point;
+mod bls;
+#[cfg(feature = "bls12_377")]
+mod bls12_377;
+mod bn254;
+#[cfg(feature = "bw6_761")]
+mod bw6_761;
 mod field;
+mod fixed_base;
+mod hash_to_curve;
+mod kzg;
+mod kzg4844;
+mod kzg7594;
+mod msm;
 mod pairing;
 mod polynomial;
+mod srs;
 pub(crate) mod utils;
 
 use pyo3::prelude::*;
-use wrapper::{Domain, Pairing, GT, Polynomial, Scalar, G1, G2};
+use wrapper::{
+    Domain, Fq, Fq12, Fq2, Fq6, G1FixedBase, G1MSMContext, G2FixedBase, G2MSMContext, G2Prepared,
+    MillerLoopOutput, Pairing, Polynomial, Scalar, G1, G2, GT, KZG, SRS,
+};
+
+/// Adds a submodule named after the Rust module `$curve`, holding the classes
+/// monomorphized for that curve and the `$extra` classes. `Fr` is an alias of `Scalar`.
+macro_rules! add_curve_submodule {
+    ($py: ident, $m: ident, $curve: ident $(, $extra: ident)*) => {{
+        let curve = PyModule::new_bound($py, stringify!($curve))?;
+        curve.add_class::<$curve::Scalar>()?;
+        curve.add("Fr", curve.getattr("Scalar")?)?;
+        curve.add_class::<$curve::Fq>()?;
+        $(curve.add_class::<$curve::$extra>()?;)*
+        curve.add_class::<$curve::G1>()?;
+        curve.add_class::<$curve::G2>()?;
+        curve.add_class::<$curve::Pairing>()?;
+        curve.add_class::<$curve::GT>()?;
+        curve.add_class::<$curve::MillerLoopOutput>()?;
+        curve.add_class::<$curve::G2Prepared>()?;
+        curve.add_class::<$curve::Polynomial>()?;
+        curve.add_class::<$curve::Domain>()?;
+        $m.add_submodule(&curve)?;
+    }};
+}
 
 /// A Python module implemented in Rust.
 #[pymodule]
-fn ark_algebra_py(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn ark_algebra_py(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Scalar>()?;
+    m.add_class::<Fq>()?;
+    m.add_class::<Fq2>()?;
+    m.add_class::<Fq6>()?;
+    m.add_class::<Fq12>()?;
     m.add_class::<G1>()?;
     m.add_class::<G2>()?;
+    m.add_class::<G1FixedBase>()?;
+    m.add_class::<G2FixedBase>()?;
+    m.add_class::<G1MSMContext>()?;
+    m.add_class::<G2MSMContext>()?;
+    // Commitments are usually computed over bases in G1.
+    m.add("MSMContext", m.getattr("G1MSMContext")?)?;
     m.add_class::<Pairing>()?;
     m.add_class::<GT>()?;
+    m.add_class::<MillerLoopOutput>()?;
+    m.add_class::<G2Prepared>()?;
     m.add_class::<Polynomial>()?;
     m.add_class::<Domain>()?;
+    m.add_class::<KZG>()?;
+    m.add_class::<SRS>()?;
+    m.add_function(wrap_pyfunction!(hash_to_curve::expand_message_xmd, m)?)?;
+
+    let bls = PyModule::new_bound(py, "bls")?;
+    bls.add_class::<bls::MinPk>()?;
+    bls.add_class::<bls::MinSig>()?;
+    m.add_submodule(&bls)?;
+
+    add_curve_submodule!(py, m, bn254, Fq2);
+    #[cfg(feature = "bls12_377")]
+    add_curve_submodule!(py, m, bls12_377, Fq2);
+    #[cfg(feature = "bw6_761")]
+    add_curve_submodule!(py, m, bw6_761);
+
+    let kzg4844 = PyModule::new_bound(py, "kzg4844")?;
+    kzg4844.add_class::<kzg4844::TrustedSetup>()?;
+    kzg4844.add("FIELD_ELEMENTS_PER_BLOB", kzg4844::FIELD_ELEMENTS_PER_BLOB)?;
+    kzg4844.add("BYTES_PER_FIELD_ELEMENT", kzg4844::BYTES_PER_FIELD_ELEMENT)?;
+    kzg4844.add("BYTES_PER_BLOB", kzg4844::BYTES_PER_BLOB)?;
+    kzg4844.add("BYTES_PER_COMMITMENT", kzg4844::BYTES_PER_COMMITMENT)?;
+    kzg4844.add("BYTES_PER_PROOF", kzg4844::BYTES_PER_PROOF)?;
+    kzg4844.add("FIELD_ELEMENTS_PER_EXT_BLOB", kzg7594::FIELD_ELEMENTS_PER_EXT_BLOB)?;
+    kzg4844.add("FIELD_ELEMENTS_PER_CELL", kzg7594::FIELD_ELEMENTS_PER_CELL)?;
+    kzg4844.add("BYTES_PER_CELL", kzg7594::BYTES_PER_CELL)?;
+    kzg4844.add("CELLS_PER_EXT_BLOB", kzg7594::CELLS_PER_EXT_BLOB)?;
+    m.add_submodule(&kzg4844)?;
 
     Ok(())
 }
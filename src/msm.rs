@@ -0,0 +1,113 @@
+use ark_ec::CurveGroup;
+use pyo3::PyResult;
+use rayon::prelude::*;
+
+use crate::utils::value_error;
+
+/// Affine bases kept in memory for repeated multi-scalar multiplications.
+pub struct MsmBases<G: CurveGroup> {
+    bases: Vec<G::Affine>,
+}
+
+impl<G: CurveGroup> MsmBases<G> {
+    pub fn new(bases: Vec<G::Affine>) -> Self {
+        Self { bases }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bases.len()
+    }
+
+    /// Computes the sum of `bases[start + i] * scalars[i]`, where `scalars` must cover
+    /// the bases from `start` to `end` exactly.
+    pub fn msm_range(&self, start: usize, end: usize, scalars: &[G::ScalarField]) -> PyResult<G> {
+        if start > end || end > self.bases.len() {
+            return Err(value_error(format!(
+                "invalid range {start}..{end} for {} bases",
+                self.bases.len()
+            )));
+        }
+        if scalars.len() != end - start {
+            return Err(value_error(format!(
+                "expected {} scalars, got {}",
+                end - start,
+                scalars.len()
+            )));
+        }
+        Ok(G::msm_unchecked(&self.bases[start..end], scalars))
+    }
+
+    pub fn msm(&self, scalars: &[G::ScalarField]) -> PyResult<G> {
+        self.msm_range(0, self.bases.len(), scalars)
+    }
+
+    pub fn batch_msm(&self, scalars: &[Vec<G::ScalarField>]) -> PyResult<Vec<G>> {
+        scalars
+            .par_iter()
+            .map(|scalars| self.msm(scalars))
+            .collect()
+    }
+}
+
+#[macro_export]
+macro_rules! monomorphize_msm {
+    ($struct: ident, $point: ident, $inner: ty, $scalar: ident) => {
+        /// A list of points converted to affine form once, to compute many
+        /// multi-scalar multiplications over the same points.
+        #[pyo3::pyclass]
+        pub struct $struct($crate::msm::MsmBases<$inner>);
+
+        #[pyo3::pymethods]
+        impl $struct {
+            #[new]
+            fn new(py: pyo3::Python, points: Vec<$point>) -> Self {
+                let points = points.into_iter().map(|point| point.0).collect();
+                let bases = py.allow_threads(|| $crate::point::Point::batch_to_affine(points));
+                Self($crate::msm::MsmBases::new(bases))
+            }
+
+            fn __len__(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Computes the sum of `points[i] * scalars[i]`. There must be exactly one
+            /// scalar per point.
+            fn msm(&self, py: pyo3::Python, scalars: Vec<$scalar>) -> pyo3::PyResult<$point> {
+                let scalars: Vec<_> = scalars.into_iter().map(|scalar| scalar.0).collect();
+                py.allow_threads(|| self.0.msm(&scalars))
+                    .map(|point| $point($crate::point::Point::Point(point)))
+            }
+
+            /// Computes the sum of `points[start + i] * scalars[i]` over the points from
+            /// `start` (inclusive) to `end` (exclusive).
+            fn msm_range(
+                &self,
+                py: pyo3::Python,
+                start: usize,
+                end: usize,
+                scalars: Vec<$scalar>,
+            ) -> pyo3::PyResult<$point> {
+                let scalars: Vec<_> = scalars.into_iter().map(|scalar| scalar.0).collect();
+                py.allow_threads(|| self.0.msm_range(start, end, &scalars))
+                    .map(|point| $point($crate::point::Point::Point(point)))
+            }
+
+            /// Computes one multi-scalar multiplication per list of scalars, in parallel.
+            fn batch_msm(
+                &self,
+                py: pyo3::Python,
+                scalars: Vec<Vec<$scalar>>,
+            ) -> pyo3::PyResult<Vec<$point>> {
+                let scalars: Vec<Vec<_>> = scalars
+                    .into_iter()
+                    .map(|scalars| scalars.into_iter().map(|scalar| scalar.0).collect())
+                    .collect();
+                let points = py.allow_threads(|| self.0.batch_msm(&scalars))?;
+                Ok(points
+                    .into_iter()
+                    .map(|point| $point($crate::point::Point::Point(point)))
+                    .collect())
+            }
+        }
+    };
+}
@@ -1,7 +1,121 @@
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::CurveGroup;
+use ark_ff::{Field, One, QuadExtConfig, QuadExtField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
+use pyo3::PyResult;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::utils::{serialisation_error_to_py_err, value_error};
+
+/// Compresses an element `c0 + c1 * w` of norm one to `m = (1 + c0) / c1`, so that
+/// `c0 + c1 * w = (m + w) / (m - w)`. This is the compression of the algebraic torus
+/// `T2`, which contains the target group. The identity is mapped to `m = 0`, which
+/// would otherwise stand for `-1`, an element outside of the target group.
+fn torus_compress<P: QuadExtConfig>(f: &QuadExtField<P>) -> P::BaseField {
+    if f.c1.is_zero() {
+        return P::BaseField::zero();
+    }
+    (P::BaseField::one() + f.c0) / f.c1
+}
+
+/// Inverts `torus_compress`. This only fails if `m^2` is the non-residue defining the
+/// extension, which cannot happen since it is not a square.
+fn torus_decompress<P: QuadExtConfig>(m: P::BaseField) -> Option<QuadExtField<P>> {
+    if m.is_zero() {
+        return Some(QuadExtField::one());
+    }
+    let m_squared = m.square();
+    let denominator = (m_squared - P::NONRESIDUE).inverse()?;
+    Some(QuadExtField::new(
+        (m_squared + P::NONRESIDUE) * denominator,
+        m.double() * denominator,
+    ))
+}
+
+pub fn to_compressed_bytes<E, P, const N: usize>(gt: &PairingOutput<E>) -> PyResult<[u8; N]>
+where
+    E: Pairing<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    let mut bytes = [0u8; N];
+    torus_compress(&gt.0)
+        .serialize_compressed(&mut bytes[..])
+        .map_err(serialisation_error_to_py_err)
+        .map(|_| bytes)
+}
+
+/// Deserializes a compressed element and checks that it lies in the target group.
+pub fn from_compressed_bytes<E, P, const N: usize>(bytes: [u8; N]) -> PyResult<PairingOutput<E>>
+where
+    E: Pairing<TargetField = QuadExtField<P>>,
+    P: QuadExtConfig,
+{
+    let m =
+        P::BaseField::deserialize_compressed(&bytes[..]).map_err(serialisation_error_to_py_err)?;
+    let gt = torus_decompress(m)
+        .map(PairingOutput)
+        .ok_or_else(|| value_error("invalid compressed target group element"))?;
+    gt.check().map_err(serialisation_error_to_py_err)?;
+    Ok(gt)
+}
+
+pub fn to_uncompressed_bytes<E: Pairing, const N: usize>(
+    gt: &PairingOutput<E>,
+) -> PyResult<[u8; N]> {
+    let mut bytes = [0u8; N];
+    gt.serialize_uncompressed(&mut bytes[..])
+        .map_err(serialisation_error_to_py_err)
+        .map(|_| bytes)
+}
+
+/// Deserializes an element of the target field and checks that it lies in the target
+/// group.
+pub fn from_uncompressed_bytes<E: Pairing, const N: usize>(
+    bytes: [u8; N],
+) -> PyResult<PairingOutput<E>> {
+    PairingOutput::deserialize_uncompressed(&bytes[..]).map_err(serialisation_error_to_py_err)
+}
+
+/// Checks that the product of the pairings of `pairs` is the identity of `GT`.
+pub fn check<E: Pairing>(pairs: Vec<(E::G1Affine, E::G2Prepared)>) -> bool {
+    let (g1s, g2s): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+    E::multi_pairing(g1s, g2s).is_zero()
+}
+
+/// Checks several pairing product equations with a single multi-pairing, by raising
+/// all but the first equation to random powers drawn from a generator seeded with
+/// `rng_seed`, or from the OS if no seed is given. A failing equation makes the
+/// combined check fail, except with negligible probability.
+pub fn batch_check<E: Pairing>(
+    equations: Vec<Vec<(E::G1, E::G2Prepared)>>,
+    rng_seed: Option<u64>,
+) -> bool {
+    let mut rng = match rng_seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut g1s = Vec::new();
+    let mut g2s = Vec::new();
+    for (i, equation) in equations.into_iter().enumerate() {
+        let r = if i == 0 {
+            E::ScalarField::one()
+        } else {
+            E::ScalarField::rand(&mut rng)
+        };
+        for (g1, g2) in equation {
+            g1s.push(g1 * r);
+            g2s.push(g2);
+        }
+    }
+    E::multi_pairing(E::G1::normalize_batch(&g1s), g2s).is_zero()
+}
+
 #[macro_export]
 macro_rules! monomorphize_pairing {
-    ($struct: ident, $inner: ty, $g1: ty, $g2: ty) => {
-        use ark_ec::pairing::{Pairing as _, PairingOutput as POutput};
+    ($struct: ident, $inner: ty, $g1: ty, $g2: ty, $COMPRESSED_SIZE: expr) => {
+        use ark_ec::pairing::{
+            MillerLoopOutput as MLOutput, Pairing as _, PairingOutput as POutput,
+        };
 
         #[derive(Copy, Clone)]
         #[pyo3::pyclass]
@@ -44,14 +158,53 @@ macro_rules! monomorphize_pairing {
                 Self(self.0.double())
             }
 
+            /// Returns the hex encoding of the compressed bytes of `self`.
             fn __str__(&self) -> pyo3::PyResult<String> {
-                Ok(format!("{}", self.0 .0))
+                let bytes: [u8; $COMPRESSED_SIZE] = $crate::pairing::to_compressed_bytes(&self.0)?;
+                Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+            }
+
+            fn __hash__(&self) -> u64 {
+                use std::hash::{Hash, Hasher};
+                let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                self.0.hash(&mut hasher);
+                hasher.finish()
+            }
+
+            /// Returns the serialized compressed bytes of `self`. Since elements of
+            /// `GT` lie in an algebraic torus, they are compressed to half the size of
+            /// an element of the target field.
+            #[allow(clippy::wrong_self_convention)]
+            fn to_compressed_bytes(&self) -> pyo3::PyResult<[u8; $COMPRESSED_SIZE]> {
+                $crate::pairing::to_compressed_bytes(&self.0)
+            }
+
+            /// Deserializes a compressed element, checking that it lies in `GT`.
+            #[staticmethod]
+            fn from_compressed_bytes(bytes: [u8; $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
+                $crate::pairing::from_compressed_bytes(bytes).map(Self)
             }
 
-            fn __richcmp__(&self, other: Self, op: pyclass::CompareOp) -> pyo3::PyResult<bool> {
+            /// Returns the serialized bytes of `self` as an element of the target field.
+            #[allow(clippy::wrong_self_convention)]
+            fn to_uncompressed_bytes(&self) -> pyo3::PyResult<[u8; 2 * $COMPRESSED_SIZE]> {
+                $crate::pairing::to_uncompressed_bytes(&self.0)
+            }
+
+            /// Deserializes an element of the target field, checking that it lies in `GT`.
+            #[staticmethod]
+            fn from_uncompressed_bytes(bytes: [u8; 2 * $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
+                $crate::pairing::from_uncompressed_bytes(bytes).map(Self)
+            }
+
+            fn __richcmp__(
+                &self,
+                other: Self,
+                op: pyo3::pyclass::CompareOp,
+            ) -> pyo3::PyResult<bool> {
                 match op {
-                    pyclass::CompareOp::Eq => Ok(self.0 == other.0),
-                    pyclass::CompareOp::Ne => Ok(self.0 != other.0),
+                    pyo3::pyclass::CompareOp::Eq => Ok(self.0 == other.0),
+                    pyo3::pyclass::CompareOp::Ne => Ok(self.0 != other.0),
                     _ => Err(pyo3::exceptions::PyValueError::new_err(
                         "comparison operator not implemented".to_owned(),
                     )),
@@ -59,32 +212,179 @@ macro_rules! monomorphize_pairing {
             }
         }
 
+        /// The output of a Miller loop, which only becomes an element of `GT` after the
+        /// final exponentiation. The outputs of several Miller loops can be multiplied
+        /// together, so that a single final exponentiation is needed.
         #[derive(Copy, Clone)]
-        #[pyclass]
+        #[pyo3::pyclass]
+        pub struct MillerLoopOutput(MLOutput<$inner>);
+
+        #[pyo3::pymethods]
+        impl MillerLoopOutput {
+            /// Returns the output of an empty Miller loop.
+            #[staticmethod]
+            pub fn one() -> Self {
+                use ark_ff::One;
+                Self(MLOutput(One::one()))
+            }
+
+            /// Multiply two Miller loop outputs.
+            fn __mul__(&self, rhs: Self) -> Self {
+                Self(MLOutput(self.0 .0 * rhs.0 .0))
+            }
+
+            fn __richcmp__(
+                &self,
+                other: Self,
+                op: pyo3::pyclass::CompareOp,
+            ) -> pyo3::PyResult<bool> {
+                match op {
+                    pyo3::pyclass::CompareOp::Eq => Ok(self.0 == other.0),
+                    pyo3::pyclass::CompareOp::Ne => Ok(self.0 != other.0),
+                    _ => Err(pyo3::exceptions::PyValueError::new_err(
+                        "comparison operator not implemented".to_owned(),
+                    )),
+                }
+            }
+        }
+
+        /// A point of G2 with the line coefficients of its Miller loop precomputed, to
+        /// speed up repeated pairings with the same point.
+        #[derive(Clone)]
+        #[pyo3::pyclass]
+        pub struct G2Prepared(<$inner as ark_ec::pairing::Pairing>::G2Prepared);
+
+        #[pyo3::pymethods]
+        impl G2Prepared {
+            #[new]
+            fn new(point: $g2) -> Self {
+                Self(point.0.to_affine().into())
+            }
+        }
+
+        /// Pairing functions take points of G2 either as they are or prepared.
+        #[allow(clippy::large_enum_variant)]
+        #[derive(pyo3::FromPyObject)]
+        enum G2OrPrepared {
+            Prepared(G2Prepared),
+            Point($g2),
+        }
+
+        impl G2OrPrepared {
+            fn prepare(self) -> <$inner as ark_ec::pairing::Pairing>::G2Prepared {
+                match self {
+                    G2OrPrepared::Prepared(prepared) => prepared.0,
+                    G2OrPrepared::Point(point) => point.0.to_affine().into(),
+                }
+            }
+        }
+
+        #[derive(Copy, Clone)]
+        #[pyo3::pyclass]
         pub struct $struct($inner);
 
-        #[pymethods]
+        #[pyo3::pymethods]
         impl $struct {
             /// Computes the product of the point-wise pairings of the
-            /// elements of `g1s` and `g2s`.
+            /// elements of `g1s` and `g2s`. The elements of `g2s` may be prepared.
             #[staticmethod]
-            fn multi_pairing(py: Python, g1s: Vec<$g1>, g2s: Vec<$g2>) -> GT {
-                py.allow_threads(|| {
-                    let g1_inner: Vec<ark_bls12_381::G1Affine> =
+            fn multi_pairing(
+                py: pyo3::Python,
+                g1s: Vec<$g1>,
+                g2s: Vec<G2OrPrepared>,
+            ) -> pyo3::PyResult<GT> {
+                if g1s.len() != g2s.len() {
+                    return Err($crate::utils::value_error(format!(
+                        "got {} G1 points but {} G2 points",
+                        g1s.len(),
+                        g2s.len()
+                    )));
+                }
+                Ok(py.allow_threads(|| {
+                    let g1_inner: Vec<<$inner as ark_ec::pairing::Pairing>::G1Affine> =
                         g1s.into_par_iter().map(|g1| g1.0.to_affine()).collect();
-                    let g2_inner: Vec<ark_bls12_381::G2Affine> =
-                        g2s.into_par_iter().map(|g2| g2.0.to_affine()).collect();
+                    let g2_inner: Vec<<$inner as ark_ec::pairing::Pairing>::G2Prepared> =
+                        g2s.into_par_iter().map(G2OrPrepared::prepare).collect();
                     GT(<$inner>::multi_pairing(g1_inner, g2_inner))
+                }))
+            }
+
+            /// Computes the product of the Miller loops of the point-wise pairs of
+            /// `g1s` and `g2s`, without the final exponentiation. The elements of `g2s`
+            /// may be prepared.
+            #[staticmethod]
+            fn miller_loop(
+                py: pyo3::Python,
+                g1s: Vec<$g1>,
+                g2s: Vec<G2OrPrepared>,
+            ) -> pyo3::PyResult<MillerLoopOutput> {
+                if g1s.len() != g2s.len() {
+                    return Err($crate::utils::value_error(format!(
+                        "got {} G1 points but {} G2 points",
+                        g1s.len(),
+                        g2s.len()
+                    )));
+                }
+                Ok(py.allow_threads(|| {
+                    let g1_inner: Vec<<$inner as ark_ec::pairing::Pairing>::G1Affine> =
+                        g1s.into_par_iter().map(|g1| g1.0.to_affine()).collect();
+                    let g2_inner: Vec<<$inner as ark_ec::pairing::Pairing>::G2Prepared> =
+                        g2s.into_par_iter().map(G2OrPrepared::prepare).collect();
+                    MillerLoopOutput(<$inner>::multi_miller_loop(g1_inner, g2_inner))
+                }))
+            }
+
+            /// Maps the output of a Miller loop to `GT`.
+            #[staticmethod]
+            fn final_exponentiation(py: pyo3::Python, mlo: MillerLoopOutput) -> pyo3::PyResult<GT> {
+                py.allow_threads(|| <$inner>::final_exponentiation(mlo.0))
+                    .map(GT)
+                    .ok_or_else(|| $crate::utils::value_error("the Miller loop output is zero"))
+            }
+
+            /// Checks that the product of the pairings of the `(g1, g2)` pairs is the
+            /// identity of `GT`. The points of G2 may be prepared.
+            #[staticmethod]
+            fn check(py: pyo3::Python, pairs: Vec<($g1, G2OrPrepared)>) -> bool {
+                py.allow_threads(|| {
+                    let pairs = pairs
+                        .into_par_iter()
+                        .map(|(g1, g2)| (g1.0.to_affine(), g2.prepare()))
+                        .collect();
+                    $crate::pairing::check::<$inner>(pairs)
                 })
             }
 
-            /// Computes the pairing `e(g1, g2)`.
+            /// Checks a list of equations, each given as a list of `(g1, g2)` pairs
+            /// like in `check`, with a single multi-pairing. The equations are combined
+            /// with random coefficients drawn from a generator seeded with `rng_seed`,
+            /// or from the OS if `rng_seed` is `None`.
             #[staticmethod]
-            fn pairing(py: Python, g1: $g1, g2: $g2) -> GT {
+            #[pyo3(signature = (equations, rng_seed = None))]
+            fn batch_check(
+                py: pyo3::Python,
+                equations: Vec<Vec<($g1, G2OrPrepared)>>,
+                rng_seed: Option<u64>,
+            ) -> bool {
                 py.allow_threads(|| {
-                    GT(<$inner>::pairing(g1.0.to_affine(), g2.0.to_affine()))
+                    let equations = equations
+                        .into_par_iter()
+                        .map(|pairs| {
+                            pairs
+                                .into_iter()
+                                .map(|(g1, g2)| (g1.0.to_group(), g2.prepare()))
+                                .collect()
+                        })
+                        .collect();
+                    $crate::pairing::batch_check::<$inner>(equations, rng_seed)
                 })
             }
+
+            /// Computes the pairing `e(g1, g2)`, where `g2` may be prepared.
+            #[staticmethod]
+            fn pairing(py: pyo3::Python, g1: $g1, g2: G2OrPrepared) -> GT {
+                py.allow_threads(|| GT(<$inner>::pairing(g1.0.to_affine(), g2.prepare())))
+            }
         }
     };
 }
\ No newline at end of file
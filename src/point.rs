@@ -1,11 +1,13 @@
 use std::collections::BTreeMap;
 
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
 use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use pyo3::{exceptions, pyclass, PyResult, Python};
 use rayon::prelude::*;
 
-use crate::utils::serialisation_error_to_py_err;
+use crate::utils::{serialisation_error_to_py_err, value_error};
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Point<G: CurveGroup> {
@@ -31,17 +33,17 @@ impl<G: CurveGroup> Point<G> {
         }
     }
 
-    pub fn to_affine(&self) -> G::Affine {
+    pub fn to_affine(self) -> G::Affine {
         match self {
-            Self::Affine(p) => *p,
+            Self::Affine(p) => p,
             Self::Point(p) => p.into_affine(),
         }
     }
 
-    pub fn to_group(&self) -> G {
+    pub fn to_group(self) -> G {
         match self {
             Self::Affine(p) => p.into_group(),
-            Self::Point(p) => *p,
+            Self::Point(p) => p,
         }
     }
 
@@ -96,7 +98,7 @@ impl<G: CurveGroup> Point<G> {
     }
 
     pub fn __str__(&self) -> PyResult<String> {
-        return Ok(format!("{}", self.to_affine()));
+        Ok(format!("{}", self.to_affine()))
     }
 
     pub fn __richcmp__(&self, other: Self, op: pyclass::CompareOp) -> PyResult<bool> {
@@ -111,7 +113,7 @@ impl<G: CurveGroup> Point<G> {
         }
     }
 
-    pub fn to_compressed_bytes<const N: usize>(&self) -> PyResult<[u8; N]> {
+    pub fn to_compressed_bytes<const N: usize>(self) -> PyResult<[u8; N]> {
         let mut bytes = [0u8; N];
         let result = match self {
             Point::Point(point) => point.serialize_compressed(&mut bytes[..]),
@@ -132,43 +134,302 @@ impl<G: CurveGroup> Point<G> {
         Ok(Self::Affine(g))
     }
 
-    pub fn msm(py: Python, points: Vec<Self>, scalars: Vec<G::ScalarField>) -> PyResult<Self> {
+    /// Deserializes compressed points in parallel. Raises a `ValueError` naming the
+    /// index of the first point that fails to deserialize.
+    pub fn from_compressed_bytes_batch<const N: usize>(
+        py: Python,
+        bytes: Vec<[u8; N]>,
+        check: bool,
+    ) -> PyResult<Vec<Self>> {
         py.allow_threads(|| {
-            let points_affine: Vec<_> = points
+            let points: Vec<_> = bytes
                 .par_iter()
-                .enumerate()
-                .filter_map(|(i, point)| point.as_affine().map(|p| (i, *p)))
+                .map(|bytes| {
+                    if check {
+                        G::Affine::deserialize_compressed(&bytes[..])
+                    } else {
+                        G::Affine::deserialize_compressed_unchecked(&bytes[..])
+                    }
+                })
                 .collect();
-            let rest: (Vec<_>, Vec<_>) = points
-                .into_par_iter()
+            points
+                .into_iter()
                 .enumerate()
-                .filter_map(|(i, point)| point.as_point().map(|p| (i, *p)))
-                .unzip();
-            let rest_affine = G::normalize_batch(&rest.1[..]);
+                .map(|(i, point)| {
+                    point.map(Self::Affine).map_err(|err| {
+                        value_error(format!(
+                            "failed to deserialise the point at index {i}: {err}"
+                        ))
+                    })
+                })
+                .collect()
+        })
+    }
 
-            let points: BTreeMap<_, _> = points_affine
-                .into_par_iter()
-                .chain(rest.0.into_par_iter().zip(rest_affine))
-                .collect();
+    pub fn to_uncompressed_bytes<const N: usize>(self) -> PyResult<[u8; N]> {
+        let mut bytes = [0u8; N];
+        let result = match self {
+            Point::Point(point) => point.serialize_uncompressed(&mut bytes[..]),
+            Point::Affine(point) => point.serialize_uncompressed(&mut bytes[..]),
+        };
+        result.map_err(serialisation_error_to_py_err).map(|_| bytes)
+    }
+
+    pub fn from_uncompressed_bytes<const N: usize>(bytes: [u8; N]) -> PyResult<Self> {
+        let g: G::Affine = CanonicalDeserialize::deserialize_uncompressed(&bytes[..])
+            .map_err(serialisation_error_to_py_err)?;
+        Ok(Self::Affine(g))
+    }
+
+    pub fn from_uncompressed_bytes_unchecked<const N: usize>(bytes: [u8; N]) -> PyResult<Self> {
+        let g: G::Affine = CanonicalDeserialize::deserialize_uncompressed_unchecked(&bytes[..])
+            .map_err(serialisation_error_to_py_err)?;
+        Ok(Self::Affine(g))
+    }
+
+    pub fn is_identity(&self) -> bool {
+        match self {
+            Point::Point(point) => point.is_zero(),
+            Point::Affine(point) => point.is_zero(),
+        }
+    }
 
-            let points: Vec<_> = points.into_par_iter().map(|(_, point)| point).collect();
+    /// Converts points to affine form, normalizing all projective points at once so
+    /// that they share a single field inversion.
+    pub fn batch_to_affine(points: Vec<Self>) -> Vec<G::Affine> {
+        let points_affine: Vec<_> = points
+            .par_iter()
+            .enumerate()
+            .filter_map(|(i, point)| point.as_affine().map(|p| (i, *p)))
+            .collect();
+        let rest: (Vec<_>, Vec<_>) = points
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(i, point)| point.as_point().map(|p| (i, *p)))
+            .unzip();
+        let rest_affine = G::normalize_batch(&rest.1[..]);
+
+        let points: BTreeMap<_, _> = points_affine
+            .into_par_iter()
+            .chain(rest.0.into_par_iter().zip(rest_affine))
+            .collect();
+
+        points.into_par_iter().map(|(_, point)| point).collect()
+    }
 
+    pub fn to_compressed_bytes_batch<const N: usize>(
+        py: Python,
+        points: Vec<Self>,
+    ) -> PyResult<Vec<[u8; N]>> {
+        py.allow_threads(|| {
+            Self::batch_to_affine(points)
+                .par_iter()
+                .map(|point| Self::Affine(*point).to_compressed_bytes())
+                .collect()
+        })
+    }
+
+    /// Computes the sum of `points[i] * scalars[i]`. With `check`, raises a
+    /// `ValueError` if the lengths differ; otherwise the longer list is truncated.
+    pub fn msm(
+        py: Python,
+        points: Vec<Self>,
+        scalars: Vec<G::ScalarField>,
+        check: bool,
+    ) -> PyResult<Self> {
+        if check {
+            check_msm_lengths(points.len(), scalars.len())?;
+        }
+        py.allow_threads(|| {
+            let points = Self::batch_to_affine(points);
             let result = G::msm_unchecked(&points, &scalars);
             Ok(Self::Point(result))
         })
     }
+
+    /// Computes the sum of `points[i] * scalars[i]` for scalars that fit in a `u64`,
+    /// only processing as many bits as the largest scalar has.
+    pub fn msm_small(py: Python, points: Vec<Self>, scalars: Vec<u64>) -> PyResult<Self> {
+        check_msm_lengths(points.len(), scalars.len())?;
+        py.allow_threads(|| {
+            let points = Self::batch_to_affine(points);
+            let scalars: Vec<_> = scalars
+                .iter()
+                .map(|scalar| (false, std::slice::from_ref(scalar)))
+                .collect();
+            Ok(Self::Point(signed_digit_msm(&points, &scalars)))
+        })
+    }
+
+    /// Computes the sum of `points[i] * scalars[i]`, where each scalar is given by its
+    /// sign and its absolute value reduced modulo the group order.
+    pub fn msm_signed(
+        py: Python,
+        points: Vec<Self>,
+        scalars: Vec<(bool, G::ScalarField)>,
+    ) -> PyResult<Self> {
+        check_msm_lengths(points.len(), scalars.len())?;
+        py.allow_threads(|| {
+            let points = Self::batch_to_affine(points);
+            let bigints: Vec<_> = scalars
+                .iter()
+                .map(|(negative, scalar)| (*negative, scalar.into_bigint()))
+                .collect();
+            let scalars: Vec<_> = bigints
+                .iter()
+                .map(|(negative, bigint)| (*negative, bigint.as_ref()))
+                .collect();
+            Ok(Self::Point(signed_digit_msm(&points, &scalars)))
+        })
+    }
+}
+
+fn check_msm_lengths(num_points: usize, num_scalars: usize) -> PyResult<()> {
+    if num_points != num_scalars {
+        return Err(value_error(format!(
+            "got {num_points} points but {num_scalars} scalars"
+        )));
+    }
+    Ok(())
+}
+
+/// Reads `width` bits of the little-endian `limbs` from bit `offset` on.
+fn read_bits(limbs: &[u64], offset: usize, width: usize) -> u64 {
+    let (index, shift) = (offset / 64, offset % 64);
+    if index >= limbs.len() {
+        return 0;
+    }
+    let mut bits = limbs[index] >> shift;
+    if shift + width > 64 && index + 1 < limbs.len() {
+        bits |= limbs[index + 1] << (64 - shift);
+    }
+    bits & ((1 << width) - 1)
+}
+
+/// Pippenger's algorithm over signed digits in `[-2^(c - 1), 2^(c - 1))`, which halves
+/// the number of buckets since subtracting a point is as cheap as adding it. Each
+/// scalar is given by its sign and its little-endian limbs, and only as many windows
+/// as needed for the largest scalar are processed.
+fn signed_digit_msm<G: CurveGroup>(bases: &[G::Affine], scalars: &[(bool, &[u64])]) -> G {
+    let num_bits = scalars
+        .iter()
+        .flat_map(|(_, limbs)| {
+            let last = limbs.iter().rposition(|limb| *limb != 0)?;
+            Some(64 * last + 64 - limbs[last].leading_zeros() as usize)
+        })
+        .max()
+        .unwrap_or(0);
+    if num_bits == 0 {
+        return G::zero();
+    }
+
+    let c = if scalars.len() < 32 {
+        3
+    } else {
+        ark_std::log2(scalars.len()) as usize * 69 / 100 + 2
+    };
+    // An extra window of zero bits absorbs the final carry.
+    let num_windows = num_bits.div_ceil(c) + 1;
+    let digits: Vec<i64> = scalars
+        .par_iter()
+        .flat_map_iter(|(negative, limbs)| {
+            let mut carry = 0;
+            (0..num_windows).map(move |i| {
+                let mut digit = read_bits(limbs, i * c, c) as i64 + carry;
+                carry = 0;
+                if digit >= 1 << (c - 1) {
+                    digit -= 1 << c;
+                    carry = 1;
+                }
+                if *negative {
+                    -digit
+                } else {
+                    digit
+                }
+            })
+        })
+        .collect();
+
+    let window_sums: Vec<G> = (0..num_windows)
+        .into_par_iter()
+        .map(|i| {
+            let mut buckets = vec![G::zero(); 1 << (c - 1)];
+            for (digits, base) in digits.chunks(num_windows).zip(bases) {
+                let digit = digits[i];
+                if digit > 0 {
+                    buckets[digit as usize - 1] += base;
+                } else if digit < 0 {
+                    buckets[(-digit) as usize - 1] -= base;
+                }
+            }
+
+            let mut running_sum = G::zero();
+            let mut sum = G::zero();
+            for bucket in buckets.into_iter().rev() {
+                running_sum += bucket;
+                sum += running_sum;
+            }
+            sum
+        })
+        .collect();
+
+    window_sums
+        .into_iter()
+        .rev()
+        .fold(G::zero(), |mut total, sum| {
+            for _ in 0..c {
+                total.double_in_place();
+            }
+            total + sum
+        })
+}
+
+impl<P: SWCurveConfig> Point<Projective<P>> {
+    /// Returns the affine coordinates of the point, or `None` for the identity.
+    pub fn xy(&self) -> Option<(P::BaseField, P::BaseField)> {
+        self.to_affine().xy().map(|(x, y)| (*x, *y))
+    }
+
+    /// Constructs a point from its affine coordinates. With `check`, the point must be
+    /// on the curve and in the prime order subgroup.
+    pub fn from_xy(x: P::BaseField, y: P::BaseField, check: bool) -> PyResult<Self> {
+        let point = Affine::<P>::new_unchecked(x, y);
+        if check && !point.is_on_curve() {
+            return Err(value_error("the point is not on the curve"));
+        }
+        if check && !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(value_error("the point is not in the correct subgroup"));
+        }
+        Ok(Self::Affine(point))
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        self.to_affine().is_on_curve()
+    }
+
+    /// Checks if the point is in the prime order subgroup. Points that are not on the
+    /// curve are not in the subgroup.
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        let point = self.to_affine();
+        point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()
+    }
+
+    pub fn clear_cofactor(&self) -> Self {
+        Self::Affine(self.to_affine().clear_cofactor())
+    }
 }
 
 #[macro_export]
 macro_rules! monomorphize_point {
-    ($struct: ident, $inner: ty, $scalar: ty, $COMPRESSED_SIZE: expr) => {
+    ($struct: ident, $inner: ty, $scalar: ty, $base: ident, $COMPRESSED_SIZE: expr) => {
         #[derive(Copy, Clone)]
-        #[pyclass]
-        pub struct $struct($crate::point::Point<$inner>);
+        #[pyo3::pyclass]
+        pub struct $struct(pub(crate) $crate::point::Point<$inner>);
 
-        #[pymethods]
+        #[pyo3::pymethods]
         impl $struct {
             pub const COMPRESSED_SIZE: usize = $COMPRESSED_SIZE;
+            pub const UNCOMPRESSED_SIZE: usize = 2 * $COMPRESSED_SIZE;
 
             /// Returns the generator of the group.
             #[new]
@@ -213,38 +474,169 @@ macro_rules! monomorphize_point {
                 Self(self.0.double())
             }
 
-            fn __str__(&self) -> PyResult<String> {
+            fn __str__(&self) -> pyo3::PyResult<String> {
                 self.0.__str__()
             }
 
-            fn __richcmp__(&self, other: Self, op: pyclass::CompareOp) -> PyResult<bool> {
+            fn __richcmp__(
+                &self,
+                other: Self,
+                op: pyo3::pyclass::CompareOp,
+            ) -> pyo3::PyResult<bool> {
                 self.0.__richcmp__(other.0, op)
             }
 
             /// Returns the serialized compressed bytes of `self`.
-            fn to_compressed_bytes(&self) -> PyResult<[u8; $COMPRESSED_SIZE]> {
+            #[allow(clippy::wrong_self_convention)]
+            fn to_compressed_bytes(&self) -> pyo3::PyResult<[u8; $COMPRESSED_SIZE]> {
                 self.0.to_compressed_bytes()
             }
 
             /// Deserializes a compressed point.
             #[staticmethod]
-            fn from_compressed_bytes(bytes: [u8; $COMPRESSED_SIZE]) -> PyResult<Self> {
+            fn from_compressed_bytes(bytes: [u8; $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
                 $crate::point::Point::from_compressed_bytes(bytes).map(Self)
             }
 
             /// Deserializes a compressed point without checking 
             /// if it is on the curve or in the correct subgroup.
             #[staticmethod]
-            fn from_compressed_bytes_unchecked(bytes: [u8; $COMPRESSED_SIZE]) -> PyResult<Self> {
+            fn from_compressed_bytes_unchecked(
+                bytes: [u8; $COMPRESSED_SIZE],
+            ) -> pyo3::PyResult<Self> {
                 $crate::point::Point::from_compressed_bytes_unchecked(bytes).map(Self)
             }
 
-            /// Computes the sum of `points[i] * scalars[i]`.
+            /// Deserializes a list of compressed points in parallel. Unless `check` is
+            /// false, the points are checked to be on the curve and in the correct
+            /// subgroup. Raises a `ValueError` with the index of the first invalid point.
+            #[staticmethod]
+            #[pyo3(signature = (bytes, check = true))]
+            fn from_compressed_bytes_batch(
+                py: pyo3::Python,
+                bytes: Vec<[u8; $COMPRESSED_SIZE]>,
+                check: bool,
+            ) -> pyo3::PyResult<Vec<Self>> {
+                $crate::point::Point::from_compressed_bytes_batch(py, bytes, check)
+                    .map(|points| points.into_iter().map(Self).collect())
+            }
+
+            /// Serializes a list of points in compressed form. This is faster than calling
+            /// `to_compressed_bytes` on each point, since the points are converted to
+            /// affine form with a single field inversion.
+            #[staticmethod]
+            fn to_compressed_bytes_batch(
+                py: pyo3::Python,
+                points: Vec<Self>,
+            ) -> pyo3::PyResult<Vec<[u8; $COMPRESSED_SIZE]>> {
+                let points = points.into_iter().map(|point| point.0).collect();
+                $crate::point::Point::to_compressed_bytes_batch(py, points)
+            }
+
+            /// Returns the serialized uncompressed bytes of `self`.
+            #[allow(clippy::wrong_self_convention)]
+            fn to_uncompressed_bytes(&self) -> pyo3::PyResult<[u8; 2 * $COMPRESSED_SIZE]> {
+                self.0.to_uncompressed_bytes()
+            }
+
+            /// Deserializes an uncompressed point.
+            #[staticmethod]
+            fn from_uncompressed_bytes(bytes: [u8; 2 * $COMPRESSED_SIZE]) -> pyo3::PyResult<Self> {
+                $crate::point::Point::from_uncompressed_bytes(bytes).map(Self)
+            }
+
+            /// Deserializes an uncompressed point without checking
+            /// if it is on the curve or in the correct subgroup.
+            #[staticmethod]
+            fn from_uncompressed_bytes_unchecked(
+                bytes: [u8; 2 * $COMPRESSED_SIZE],
+            ) -> pyo3::PyResult<Self> {
+                $crate::point::Point::from_uncompressed_bytes_unchecked(bytes).map(Self)
+            }
+
+            /// Returns the affine x-coordinate of `self`, or `None` for the identity.
+            fn x(&self) -> Option<$base> {
+                self.0.xy().map(|(x, _)| $base(x))
+            }
+
+            /// Returns the affine y-coordinate of `self`, or `None` for the identity.
+            fn y(&self) -> Option<$base> {
+                self.0.xy().map(|(_, y)| $base(y))
+            }
+
+            /// Checks if `self` is the identity of the group.
+            fn is_identity(&self) -> bool {
+                self.0.is_identity()
+            }
+
+            /// Checks if `self` satisfies the curve equation.
+            fn is_on_curve(&self) -> bool {
+                self.0.is_on_curve()
+            }
+
+            /// Checks if `self` is on the curve and in the prime order subgroup.
+            fn is_in_correct_subgroup(&self) -> bool {
+                self.0.is_in_correct_subgroup()
+            }
+
+            /// Maps `self` into the prime order subgroup by multiplying it by the
+            /// cofactor of the curve (or the effective cofactor, where one is defined).
+            fn clear_cofactor(&self) -> Self {
+                Self(self.0.clear_cofactor())
+            }
+
+            /// Constructs a point from its affine coordinates. Unless `check` is false,
+            /// raises a `ValueError` if the point is not on the curve or not in the
+            /// correct subgroup.
             #[staticmethod]
-            fn msm(py: Python, points: Vec<Self>, scalars: Vec<Scalar>) -> PyResult<Self> {
+            #[pyo3(signature = (x, y, check = true))]
+            fn from_xy(x: $base, y: $base, check: bool) -> pyo3::PyResult<Self> {
+                $crate::point::Point::from_xy(x.0, y.0, check).map(Self)
+            }
+
+            /// Computes the sum of `points[i] * scalars[i]`. Unless `check` is false,
+            /// raises a `ValueError` if `points` and `scalars` have different lengths;
+            /// otherwise the longer list is truncated.
+            #[staticmethod]
+            #[pyo3(signature = (points, scalars, check = true))]
+            fn msm(
+                py: pyo3::Python,
+                points: Vec<Self>,
+                scalars: Vec<$scalar>,
+                check: bool,
+            ) -> pyo3::PyResult<Self> {
                 let points = points.into_iter().map(|point| point.0).collect();
                 let scalars = scalars.into_iter().map(|scalar| scalar.0).collect();
-                $crate::point::Point::msm(py, points, scalars).map(Self)
+                $crate::point::Point::msm(py, points, scalars, check).map(Self)
+            }
+
+            /// Computes the sum of `points[i] * scalars[i]` for scalars given as
+            /// integers below `2^64`. This is faster than `msm` for small scalars.
+            #[staticmethod]
+            fn msm_small(
+                py: pyo3::Python,
+                points: Vec<Self>,
+                scalars: Vec<u64>,
+            ) -> pyo3::PyResult<Self> {
+                let points = points.into_iter().map(|point| point.0).collect();
+                $crate::point::Point::msm_small(py, points, scalars).map(Self)
+            }
+
+            /// Computes the sum of `points[i] * scalars[i]` for scalars given as Python
+            /// integers, which may be negative. Small and negative scalars are cheap,
+            /// since only the bits of the largest absolute value are processed.
+            #[staticmethod]
+            fn msm_signed(
+                py: pyo3::Python,
+                points: Vec<Self>,
+                scalars: Vec<pyo3::Bound<'_, pyo3::types::PyLong>>,
+            ) -> pyo3::PyResult<Self> {
+                let points = points.into_iter().map(|point| point.0).collect();
+                let scalars = scalars
+                    .iter()
+                    .map($crate::utils::int_to_sign_and_field)
+                    .collect::<pyo3::PyResult<_>>()?;
+                $crate::point::Point::msm_signed(py, points, scalars).map(Self)
             }
         }
     };
@@ -8,10 +8,10 @@ macro_rules! monomorphize_poly {
         };
 
         #[derive(Clone)]
-        #[pyclass]
+        #[pyo3::pyclass]
         pub struct Domain(Radix2EvaluationDomain<$field>);
 
-        #[pymethods]
+        #[pyo3::pymethods]
         impl Domain {
             #[new]
             fn new(size: usize) -> Self {
@@ -46,10 +46,10 @@ macro_rules! monomorphize_poly {
         }
 
         #[derive(Clone)]
-        #[pyclass]
+        #[pyo3::pyclass]
         pub struct Polynomial(Poly<'static, $field>);
 
-        #[pymethods]
+        #[pyo3::pymethods]
         impl Polynomial {
             /// Returns the polynomial `X`.
             #[staticmethod]
@@ -133,7 +133,7 @@ macro_rules! monomorphize_poly {
 
             fn __mul__(&self, rhs: Self) -> Self {
                 match (&self.0, &rhs.0) {
-                    (Poly::SPolynomial(a), Poly::SPolynomial(b)) => Self((a.deref().mul(b.deref()).into())),
+                    (Poly::SPolynomial(a), Poly::SPolynomial(b)) => Self(a.deref().mul(b.deref()).into()),
                     (Poly::DPolynomial(a), Poly::DPolynomial(b)) => Self((a.deref() * b.deref()).into()),
                     (Poly::SPolynomial(a), Poly::DPolynomial(b)) | (Poly::DPolynomial(b), Poly::SPolynomial(a)) => Self((&DensePolynomial::from(a.clone().into_owned()) * b.deref()).into()),
                 }
@@ -150,7 +150,9 @@ macro_rules! monomorphize_poly {
                 self.0
                     .divide_with_q_and_r(&rhs.0)
                     .map(|(q, r)| (Self(q.into()), Self(r.into())))
-                    .ok_or(exceptions::PyZeroDivisionError::new_err("division by zero"))
+                    .ok_or(pyo3::exceptions::PyZeroDivisionError::new_err(
+                        "division by zero",
+                    ))
             }
 
             fn __repr__(&self) -> String {
@@ -188,9 +190,9 @@ macro_rules! monomorphize_poly {
                     (Poly::SPolynomial(a), Poly::DPolynomial(b)) | (Poly::DPolynomial(b), Poly::SPolynomial(a)) => &DensePolynomial::from(a.clone().into_owned()) == b.deref(),
                 };
                 match op {
-                    pyclass::CompareOp::Eq => Ok(is_eq),
-                    pyclass::CompareOp::Ne => Ok(!is_eq),
-                    _ => Err(exceptions::PyValueError::new_err(
+                    pyo3::pyclass::CompareOp::Eq => Ok(is_eq),
+                    pyo3::pyclass::CompareOp::Ne => Ok(!is_eq),
+                    _ => Err(pyo3::exceptions::PyValueError::new_err(
                         "comparison operator not implemented".to_owned(),
                     )),
                 }
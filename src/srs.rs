@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group, VariableBaseMSM};
+use ark_ff::{Field, Zero};
+use ark_serialize::CanonicalDeserialize;
+use pyo3::PyResult;
+
+use crate::kzg::powers_of_tau;
+use crate::kzg4844::parse_points;
+use crate::utils::value_error;
+
+fn hex_points<'a>(value: &'a serde_json::Value, key: &str) -> PyResult<Vec<&'a str>> {
+    value[key]
+        .as_array()
+        .and_then(|points| points.iter().map(|p| p.as_str()).collect())
+        .ok_or_else(|| value_error(format!("`{key}` must be a list of strings")))
+}
+
+/// Returns the first `count` powers of a random field element, to combine many pairing
+/// checks into one.
+fn random_powers<F: Field>(count: usize) -> Vec<F> {
+    let r = F::rand(&mut ark_std::rand::thread_rng());
+    std::iter::successors(Some(F::one()), |power| Some(*power * r))
+        .take(count)
+        .collect()
+}
+
+/// The witness that a powers-of-tau ceremony was built up by successive contributions:
+/// `running_products[i] = tau_1 * ... * tau_i * G` and `pot_pubkeys[i] = tau_i * H`,
+/// where the entries at index zero are the generators.
+pub struct Witness<E: Pairing> {
+    pub(crate) running_products: Vec<E::G1Affine>,
+    pub(crate) pot_pubkeys: Vec<E::G2Affine>,
+}
+
+impl<E: Pairing> Witness<E> {
+    /// Checks that each contribution builds on the previous one, i.e. that
+    /// `e(running_products[i], H) = e(running_products[i - 1], pot_pubkeys[i])`, that
+    /// the chain ends in `tau * G`, and that no `pot_pubkeys` entry is repeated.
+    fn verify(&self, tau_g1: E::G1Affine) -> bool {
+        let n = self.running_products.len();
+        if n == 0
+            || self.pot_pubkeys.len() != n
+            || self.running_products[0] != E::G1Affine::generator()
+            || self.pot_pubkeys[0] != E::G2Affine::generator()
+            || self.running_products[n - 1] != tau_g1
+            || self.pot_pubkeys.iter().any(|pubkey| pubkey.is_zero())
+        {
+            return false;
+        }
+        let mut seen = HashSet::with_capacity(n);
+        if !self.pot_pubkeys.iter().all(|pubkey| seen.insert(pubkey)) {
+            return false;
+        }
+
+        let r_powers = random_powers::<E::ScalarField>(n - 1);
+        let lhs = E::G1::msm_unchecked(&self.running_products[1..], &r_powers);
+        let mut g1s: Vec<E::G1> = self.running_products[..n - 1]
+            .iter()
+            .zip(&r_powers)
+            .map(|(running_product, r)| *running_product * r)
+            .collect();
+        g1s.push(-lhs);
+        let mut g2s = self.pot_pubkeys[1..].to_vec();
+        g2s.push(E::G2Affine::generator());
+        E::multi_pairing(E::G1::normalize_batch(&g1s), g2s).is_zero()
+    }
+}
+
+/// A structured reference string of powers of a secret `tau` in G1 and G2.
+pub struct Srs<E: Pairing> {
+    pub(crate) g1_powers: Vec<E::G1Affine>,
+    pub(crate) g2_powers: Vec<E::G2Affine>,
+    pub(crate) witness: Option<Witness<E>>,
+}
+
+impl<E: Pairing> Srs<E> {
+    /// Generates the powers of `tau`. This is only meant for testing, since whoever
+    /// knows `tau` can forge proofs.
+    pub fn from_tau(
+        num_g1_powers: usize,
+        num_g2_powers: usize,
+        tau: E::ScalarField,
+    ) -> PyResult<Self> {
+        Self::new(
+            powers_of_tau(E::G1::generator(), tau, num_g1_powers),
+            powers_of_tau(E::G2::generator(), tau, num_g2_powers),
+        )
+    }
+
+    pub fn new(g1_powers: Vec<E::G1Affine>, g2_powers: Vec<E::G2Affine>) -> PyResult<Self> {
+        if g1_powers.len() < 2 || g2_powers.len() < 2 {
+            return Err(value_error(
+                "at least two powers of tau are needed in each group",
+            ));
+        }
+        Ok(Self {
+            g1_powers,
+            g2_powers,
+            witness: None,
+        })
+    }
+
+    /// Parses the sub-ceremony with `num_g1_powers` powers in G1 from a transcript in
+    /// the JSON format of the Ethereum KZG ceremony. All points are subgroup checked.
+    pub fn from_transcript_json(json: &str, num_g1_powers: usize) -> PyResult<Self>
+    where
+        E::G1Affine: CanonicalDeserialize,
+        E::G2Affine: CanonicalDeserialize,
+    {
+        let json: serde_json::Value = serde_json::from_str(json)
+            .map_err(|err| value_error(format!("invalid transcript JSON: {err}")))?;
+        let transcript = json["transcripts"]
+            .as_array()
+            .ok_or_else(|| value_error("the transcript has no `transcripts` list"))?
+            .iter()
+            .find(|transcript| transcript["numG1Powers"].as_u64() == Some(num_g1_powers as u64))
+            .ok_or_else(|| {
+                value_error(format!(
+                    "the transcript has no sub-ceremony with {num_g1_powers} G1 powers"
+                ))
+            })?;
+        let powers = &transcript["powersOfTau"];
+        let witness = &transcript["witness"];
+        let g1_powers = hex_points(powers, "G1Powers")?;
+        let g2_powers = hex_points(powers, "G2Powers")?;
+        let running_products = hex_points(witness, "runningProducts")?;
+        let pot_pubkeys = hex_points(witness, "potPubkeys")?;
+        let mut srs = Self::new(parse_points(&g1_powers)?, parse_points(&g2_powers)?)?;
+        srs.witness = Some(Witness {
+            running_products: parse_points(&running_products)?,
+            pot_pubkeys: parse_points(&pot_pubkeys)?,
+        });
+        Ok(srs)
+    }
+
+    /// Checks that the G1 and G2 powers are powers of the same `tau`, and that the
+    /// ceremony witness, if any, is a valid chain of contributions ending in `tau`.
+    pub fn verify(&self) -> bool {
+        let (g1, g2) = (&self.g1_powers, &self.g2_powers);
+        if g1[0] != E::G1Affine::generator() || g2[0] != E::G2Affine::generator() || g1[1].is_zero()
+        {
+            return false;
+        }
+
+        // e(g1[i + 1], g2[0]) = e(g1[i], g2[1]) for all i.
+        let r_powers = random_powers::<E::ScalarField>(g1.len() - 1);
+        let g1_lhs = E::G1::msm_unchecked(&g1[1..], &r_powers);
+        let g1_rhs = E::G1::msm_unchecked(&g1[..g1.len() - 1], &r_powers);
+        let g1_powers_ok = E::multi_pairing(
+            [g1_lhs.into_affine(), (-g1_rhs).into_affine()],
+            [g2[0], g2[1]],
+        )
+        .is_zero();
+
+        // e(g1[0], g2[i + 1]) = e(g1[1], g2[i]) for all i.
+        let r_powers = random_powers::<E::ScalarField>(g2.len() - 1);
+        let g2_lhs = E::G2::msm_unchecked(&g2[1..], &r_powers);
+        let g2_rhs = E::G2::msm_unchecked(&g2[..g2.len() - 1], &r_powers);
+        let g2_powers_ok = E::multi_pairing(
+            [g1[0], (-g1[1].into_group()).into_affine()],
+            [g2_lhs.into_affine(), g2_rhs.into_affine()],
+        )
+        .is_zero();
+
+        g1_powers_ok
+            && g2_powers_ok
+            && self
+                .witness
+                .as_ref()
+                .is_none_or(|witness| witness.verify(g1[1]))
+    }
+}
+
+#[macro_export]
+macro_rules! monomorphize_srs {
+    ($struct: ident, $pairing: ty, $g1: ident, $g2: ident, $scalar: ident) => {
+        /// A structured reference string of powers of tau in G1 and G2.
+        #[allow(clippy::upper_case_acronyms)]
+        #[pyo3::pyclass]
+        pub struct $struct(pub(crate) $crate::srs::Srs<$pairing>);
+
+        #[pyo3::pymethods]
+        impl $struct {
+            /// Generates `num_g1_powers` and `num_g2_powers` powers of the secret `tau`.
+            /// This is only meant for testing.
+            #[staticmethod]
+            fn from_tau(
+                py: pyo3::Python,
+                num_g1_powers: usize,
+                num_g2_powers: usize,
+                tau: $scalar,
+            ) -> pyo3::PyResult<Self> {
+                py.allow_threads(|| $crate::srs::Srs::from_tau(num_g1_powers, num_g2_powers, tau.0))
+                    .map(Self)
+            }
+
+            /// Constructs an SRS from lists of powers of tau in G1 and G2.
+            #[staticmethod]
+            fn from_powers(g1_powers: Vec<$g1>, g2_powers: Vec<$g2>) -> pyo3::PyResult<Self> {
+                $crate::srs::Srs::new(
+                    g1_powers.iter().map(|p| p.0.to_affine()).collect(),
+                    g2_powers.iter().map(|p| p.0.to_affine()).collect(),
+                )
+                .map(Self)
+            }
+
+            /// Parses the sub-ceremony with `num_g1_powers` powers in G1 from the JSON
+            /// transcript of the Ethereum KZG ceremony.
+            #[staticmethod]
+            #[pyo3(signature = (json, num_g1_powers = 4096))]
+            fn from_transcript_json(
+                py: pyo3::Python,
+                json: &str,
+                num_g1_powers: usize,
+            ) -> pyo3::PyResult<Self> {
+                py.allow_threads(|| $crate::srs::Srs::from_transcript_json(json, num_g1_powers))
+                    .map(Self)
+            }
+
+            /// Loads the sub-ceremony with `num_g1_powers` powers in G1 from a file
+            /// holding the JSON transcript of the Ethereum KZG ceremony.
+            #[staticmethod]
+            #[pyo3(signature = (path, num_g1_powers = 4096))]
+            fn load_transcript(
+                py: pyo3::Python,
+                path: &str,
+                num_g1_powers: usize,
+            ) -> pyo3::PyResult<Self> {
+                let json = std::fs::read_to_string(path)
+                    .map_err(|err| pyo3::exceptions::PyIOError::new_err(err.to_string()))?;
+                Self::from_transcript_json(py, &json, num_g1_powers)
+            }
+
+            /// Returns the powers of tau in G1.
+            fn g1_powers(&self) -> Vec<$g1> {
+                self.0
+                    .g1_powers
+                    .iter()
+                    .map(|p| $g1($crate::point::Point::Affine(*p)))
+                    .collect()
+            }
+
+            /// Returns the powers of tau in G2.
+            fn g2_powers(&self) -> Vec<$g2> {
+                self.0
+                    .g2_powers
+                    .iter()
+                    .map(|p| $g2($crate::point::Point::Affine(*p)))
+                    .collect()
+            }
+
+            /// Checks with pairings that the G1 and G2 powers are consistent powers of
+            /// the same tau, and that the ceremony witness, if present, is a valid chain
+            /// of contributions. The participants' signatures are not checked.
+            fn verify(&self, py: pyo3::Python) -> bool {
+                py.allow_threads(|| self.0.verify())
+            }
+        }
+    };
+}
@@ -1,5 +1,8 @@
+use ark_ec::hashing::HashToCurveError;
+use ark_ff::{BigInteger, PrimeField};
 use ark_serialize::SerializationError;
-use pyo3::PyErr;
+use pyo3::types::{PyAnyMethods, PyBytes, PyLong};
+use pyo3::{Bound, PyAny, PyErr, PyResult, Python};
 
 pub fn serialisation_error_to_py_err(serialisation_error: SerializationError) -> PyErr {
     use pyo3::exceptions::{PyIOError, PyValueError};
@@ -17,6 +20,68 @@ pub fn serialisation_error_to_py_err(serialisation_error: SerializationError) ->
     }
 }
 
+pub fn value_error(msg: impl Into<String>) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(wrap_err_string(msg.into()))
+}
+
+pub fn hash_to_curve_error_to_py_err(hash_to_curve_error: HashToCurveError) -> PyErr {
+    use pyo3::exceptions::PyValueError;
+    PyValueError::new_err(wrap_err_string(hash_to_curve_error.to_string()))
+}
+
 fn wrap_err_string(err: String) -> String {
     format!("Err From Rust: {err}")
 }
+
+/// Splits a Python integer into its sign and the little-endian bytes of its absolute
+/// value.
+fn int_to_sign_and_bytes(value: &Bound<'_, PyLong>) -> PyResult<(bool, Vec<u8>)> {
+    let negative = value.lt(0)?;
+    let magnitude = value.call_method0("__abs__")?;
+    let num_bits: usize = magnitude.call_method0("bit_length")?.extract()?;
+    let bytes = magnitude
+        .call_method1("to_bytes", (num_bits.div_ceil(8), "little"))?
+        .extract()?;
+    Ok((negative, bytes))
+}
+
+/// Splits a Python integer into its sign and its absolute value reduced modulo the
+/// characteristic of `F`.
+pub fn int_to_sign_and_field<F: PrimeField>(value: &Bound<'_, PyLong>) -> PyResult<(bool, F)> {
+    let (negative, bytes) = int_to_sign_and_bytes(value)?;
+    Ok((negative, F::from_le_bytes_mod_order(&bytes)))
+}
+
+/// Splits a Python integer into its sign and the little-endian 64-bit limbs of its
+/// absolute value.
+pub fn int_to_sign_and_limbs(value: &Bound<'_, PyLong>) -> PyResult<(bool, Vec<u64>)> {
+    let (negative, bytes) = int_to_sign_and_bytes(value)?;
+    let limbs = bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut limb = [0u8; 8];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(limb)
+        })
+        .collect();
+    Ok((negative, limbs))
+}
+
+/// Converts a Python integer into an element of `F`, reducing it modulo the
+/// characteristic. With `strict`, raises a `ValueError` unless `0 <= value < modulus`.
+pub fn int_to_field<F: PrimeField>(value: &Bound<'_, PyLong>, strict: bool) -> PyResult<F> {
+    if strict && (value.lt(0)? || value.ge(bigint_to_int(value.py(), F::MODULUS)?)?) {
+        return Err(value_error(format!(
+            "{value} is not in the range [0, modulus)"
+        )));
+    }
+    let (negative, magnitude) = int_to_sign_and_field::<F>(value)?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Converts a big integer into a Python integer.
+pub fn bigint_to_int(py: Python<'_>, value: impl BigInteger) -> PyResult<Bound<'_, PyAny>> {
+    let bytes = PyBytes::new_bound(py, &value.to_bytes_le());
+    py.get_type_bound::<PyLong>()
+        .call_method1("from_bytes", (bytes, "little"))
+}
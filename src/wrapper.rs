@@ -6,9 +6,50 @@ use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 const SCALAR_SIZE: usize = 32;
 
 crate::monomorphize_field!(Scalar, Fr, SCALAR_SIZE);
-crate::monomorphize_point!(G1, G1Projective, Scalar, 48);
-crate::monomorphize_point!(G2, G2Projective, Scalar, 96);
+crate::monomorphize_field!(Fq, ark_bls12_381::Fq, 48);
+crate::monomorphize_prime_field!(Scalar, Fr, SCALAR_SIZE);
+crate::monomorphize_prime_field!(Fq, ark_bls12_381::Fq, 48);
+crate::monomorphize_field!(Fq2, ark_bls12_381::Fq2, 96);
+crate::monomorphize_field!(Fq6, ark_bls12_381::Fq6, 288);
+crate::monomorphize_field!(Fq12, ark_bls12_381::Fq12, 576);
+crate::monomorphize_extension_field!(Fq2, ark_bls12_381::Fq2, Fq, c0, c1);
+crate::monomorphize_extension_field!(Fq6, ark_bls12_381::Fq6, Fq2, c0, c1, c2);
+crate::monomorphize_extension_field!(Fq12, ark_bls12_381::Fq12, Fq6, c0, c1);
+crate::monomorphize_sqrt!(Scalar, Fr);
+crate::monomorphize_sqrt!(Fq, ark_bls12_381::Fq);
+crate::monomorphize_sqrt!(Fq2, ark_bls12_381::Fq2);
+crate::monomorphize_point!(G1, G1Projective, Scalar, Fq, 48);
+crate::monomorphize_point!(G2, G2Projective, Scalar, Fq2, 96);
 
-crate::monomorphize_pairing!(Pairing, ark_bls12_381::Bls12_381, G1, G2);
+crate::monomorphize_fixed_base!(G1FixedBase, G1, G1Projective, Scalar);
+crate::monomorphize_fixed_base!(G2FixedBase, G2, G2Projective, Scalar);
+
+crate::monomorphize_msm!(G1MSMContext, G1, G1Projective, Scalar);
+crate::monomorphize_msm!(G2MSMContext, G2, G2Projective, Scalar);
+
+crate::monomorphize_hash_to_curve!(G1, ark_bls12_381::g1::Config);
+crate::monomorphize_hash_to_curve!(G2, ark_bls12_381::g2::Config);
+
+crate::monomorphize_pairing!(Pairing, ark_bls12_381::Bls12_381, G1, G2, 288);
 
 crate::monomorphize_poly!(Fr, Scalar);
+
+crate::monomorphize_srs!(SRS, ark_bls12_381::Bls12_381, G1, G2, Scalar);
+crate::monomorphize_kzg!(
+    KZG,
+    ark_bls12_381::Bls12_381,
+    G1,
+    G2,
+    Scalar,
+    Polynomial,
+    SRS
+);
+
+#[pyo3::pymethods]
+impl GT {
+    /// Returns the representation of `self` as an element of `Fq12`.
+    #[allow(clippy::wrong_self_convention)]
+    fn to_fq12(&self) -> Fq12 {
+        Fq12(self.0 .0)
+    }
+}